@@ -0,0 +1,64 @@
+//! The `gen_uuid!` macro, re-exported by `crockford-uuid` behind its `macros` feature.
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+use ring::rand::SecureRandom;
+
+const BYTE_SIZE: usize = 15;
+// must match `SEED_TAG` in crockford-uuid, see `Uuid::from_seed`
+const SEED_TAG: &[u8] = b"crockford-uuid/seed/v1";
+
+/// Expands to a constant `Uuid` expression. `gen_uuid!()` picks random bytes when the
+/// macro is expanded, so the id is fixed for that build only; `gen_uuid!("seed")` is
+/// `Uuid::from_seed("seed")`, the same id in every build and release.
+#[proc_macro]
+pub fn gen_uuid(input: TokenStream) -> TokenStream {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let bytes = match tokens.as_slice() {
+        [] => random_bytes(),
+        [TokenTree::Literal(literal)] => match string_literal(literal) {
+            Some(seed) => seeded_bytes(&seed),
+            None => return compile_error("gen_uuid! expects a string literal without escapes"),
+        },
+        _ => return compile_error("gen_uuid! expects nothing or a single string literal"),
+    };
+    let bytes: Vec<String> = bytes.iter().map(|b| format!("{}u8", b)).collect();
+    format!(
+        "::crockford_uuid::Uuid::from_byte_array([{}])",
+        bytes.join(", ")
+    )
+    .parse()
+    .unwrap()
+}
+
+fn random_bytes() -> [u8; BYTE_SIZE] {
+    let mut bytes = [0u8; BYTE_SIZE];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("failed to generate random bytes");
+    bytes
+}
+
+fn seeded_bytes(seed: &str) -> [u8; BYTE_SIZE] {
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    context.update(SEED_TAG);
+    context.update(seed.as_bytes());
+    let mut bytes = [0u8; BYTE_SIZE];
+    bytes.copy_from_slice(&context.finish().as_ref()[..BYTE_SIZE]);
+    bytes
+}
+
+// the contents of a plain or raw string literal, `None` for anything else or for escapes
+fn string_literal(literal: &Literal) -> Option<String> {
+    let repr = literal.to_string();
+    if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let inner = raw.get(hashes..raw.len() - hashes)?;
+        return Some(inner.strip_prefix('"')?.strip_suffix('"')?.to_string());
+    }
+    let inner = repr.strip_prefix('"')?.strip_suffix('"')?;
+    (!inner.contains('\\')).then(|| inner.to_string())
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({:?})", message).parse().unwrap()
+}
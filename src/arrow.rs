@@ -0,0 +1,65 @@
+//! Conversions between uuids and Apache Arrow arrays.
+//!
+//! Uuids are stored as `FixedSizeBinary(15)` values, their raw bytes. The `Utf8`
+//! helpers read and write the canonical strings, for migrating existing columns.
+
+use crate::error::UuidError;
+use crate::uuid::{Uuid, BYTE_SIZE};
+use arrow_array::{Array, FixedSizeBinaryArray, StringArray};
+
+/// A `FixedSizeBinary(15)` array without nulls.
+pub fn to_fixed_size_binary<I>(uuids: I) -> FixedSizeBinaryArray
+where
+    I: IntoIterator<Item = Uuid>,
+{
+    to_fixed_size_binary_opt(uuids.into_iter().map(Some))
+}
+
+/// A `FixedSizeBinary(15)` array with a null for every `None`.
+pub fn to_fixed_size_binary_opt<I>(uuids: I) -> FixedSizeBinaryArray
+where
+    I: IntoIterator<Item = Option<Uuid>>,
+{
+    let values = uuids
+        .into_iter()
+        .map(|uuid| uuid.map(|uuid| uuid.to_byte_array()));
+    FixedSizeBinaryArray::try_from_sparse_iter_with_size(values, BYTE_SIZE as i32)
+        .expect("every value is 15 bytes")
+}
+
+/// Reads a `FixedSizeBinary(15)` array, recomputing each checksum. Fails with
+/// [`UuidError::InvalidByteLength`] for any other width.
+pub fn from_fixed_size_binary(
+    array: &FixedSizeBinaryArray,
+) -> Result<Vec<Option<Uuid>>, UuidError> {
+    if array.value_length() != BYTE_SIZE as i32 {
+        return Err(UuidError::InvalidByteLength(array.value_length() as usize));
+    }
+    (0..array.len())
+        .map(|i| {
+            if array.is_null(i) {
+                return Ok(None);
+            }
+            Uuid::from_slice(array.value(i)).map(Some)
+        })
+        .collect()
+}
+
+/// A `Utf8` array of canonical strings with a null for every `None`.
+pub fn to_utf8<I>(uuids: I) -> StringArray
+where
+    I: IntoIterator<Item = Option<Uuid>>,
+{
+    uuids
+        .into_iter()
+        .map(|uuid| uuid.map(|uuid| uuid.to_string_stack()))
+        .collect()
+}
+
+/// Parses a `Utf8` array, verifying each checksum.
+pub fn from_utf8(array: &StringArray) -> Result<Vec<Option<Uuid>>, UuidError> {
+    array
+        .iter()
+        .map(|s| s.map(Uuid::try_from).transpose())
+        .collect()
+}
@@ -0,0 +1,57 @@
+use crate::error::UuidError;
+use crate::uuid::{Uuid, BYTE_SIZE};
+use apache_avro::types::Value;
+use apache_avro::Schema;
+
+const AVRO_FIXED_SCHEMA: &str = r#"{"type": "fixed", "name": "CrockfordUuid", "size": 15}"#;
+
+impl Uuid {
+    /// The Avro schema of the `fixed(15)` form written by [`Uuid::to_avro_value`].
+    pub fn avro_fixed_schema() -> &'static str {
+        AVRO_FIXED_SCHEMA
+    }
+
+    /// The Avro value for a field with the writer schema `schema`: the raw bytes for a
+    /// `fixed(15)`, the canonical string for a `string`. In a union the first such
+    /// branch is used, e.g. for `["null", "string"]`.
+    pub fn to_avro_value(&self, schema: &Schema) -> Result<Value, UuidError> {
+        match schema {
+            Schema::Fixed(fixed) if fixed.size == BYTE_SIZE => {
+                Ok(Value::Fixed(BYTE_SIZE, self.to_byte_array().to_vec()))
+            }
+            Schema::Fixed(fixed) => Err(UuidError::InvalidByteLength(fixed.size)),
+            Schema::String => Ok(Value::String(self.to_string())),
+            Schema::Union(union) => union
+                .variants()
+                .iter()
+                .enumerate()
+                .find_map(|(i, variant)| {
+                    let value = self.to_avro_value(variant).ok()?;
+                    Some(Value::Union(i as u32, Box::new(value)))
+                })
+                .ok_or_else(|| {
+                    UuidError::InvalidEncoding(
+                        "union has no fixed(15) or string branch".to_string(),
+                    )
+                }),
+            other => Err(UuidError::InvalidEncoding(format!(
+                "avro schema {:?} can't hold a uuid",
+                other
+            ))),
+        }
+    }
+
+    /// Reads a `fixed(15)` or `string` Avro value, unwrapping unions. Strings must carry
+    /// a valid checksum.
+    pub fn from_avro_value(value: &Value) -> Result<Self, UuidError> {
+        match value {
+            Value::Fixed(_, bytes) | Value::Bytes(bytes) => Uuid::from_slice(bytes),
+            Value::String(s) => Uuid::try_from(s.as_str()),
+            Value::Union(_, value) => Uuid::from_avro_value(value),
+            other => Err(UuidError::InvalidEncoding(format!(
+                "avro value {:?} is not a uuid",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::encode::ENCODED_LEN;
+use crate::error::UuidError;
+use crate::uuid::Uuid;
+
+/// How many failures [`validate_batch`] keeps.
+pub const DEFAULT_MAX_FAILURES: usize = 1000;
+
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 16 * 1024;
+
+/// The outcome of validating many ids with [`validate_batch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport<'a> {
+    /// Number of ids checked.
+    pub total: usize,
+    /// Number of ids that failed to parse.
+    pub invalid: usize,
+    /// The index, input and error of the first failures, in input order, up to the cap.
+    pub failures: Vec<(usize, &'a str, UuidError)>,
+}
+
+impl<'a> ValidationReport<'a> {
+    fn empty() -> Self {
+        ValidationReport {
+            total: 0,
+            invalid: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Number of ids that parsed.
+    pub fn valid(&self) -> usize {
+        self.total - self.invalid
+    }
+
+    /// Whether every id parsed, including when there were none.
+    pub fn is_all_valid(&self) -> bool {
+        self.invalid == 0
+    }
+
+    /// Whether failures were left out of [`ValidationReport::failures`] for the cap.
+    pub fn is_truncated(&self) -> bool {
+        self.invalid > self.failures.len()
+    }
+
+    fn check(&mut self, index: usize, id: &'a str, max_failures: usize) {
+        self.total += 1;
+        // `from_ascii` doesn't allocate, on success or failure
+        if let Err(err) = Uuid::from_ascii(id.as_bytes()) {
+            self.invalid += 1;
+            if self.failures.len() < max_failures {
+                self.failures.push((index, id, err));
+            }
+        }
+    }
+
+    // `self` must cover the ids before `other`'s
+    #[cfg(feature = "rayon")]
+    fn merge(mut self, other: Self, max_failures: usize) -> Self {
+        self.total += other.total;
+        self.invalid += other.invalid;
+        let room = max_failures - self.failures.len().min(max_failures);
+        self.failures.extend(other.failures.into_iter().take(room));
+        self
+    }
+}
+
+/// Parses every id, counting failures and keeping the first
+/// [`DEFAULT_MAX_FAILURES`] of them. Indexes count from 0 in iteration order.
+///
+/// Each id is checked with [`Uuid::from_ascii`], which doesn't allocate, so the only
+/// allocation is the list of failures.
+pub fn validate_batch<'a>(ids: impl Iterator<Item = &'a str>) -> ValidationReport<'a> {
+    validate_batch_with_max_failures(ids, DEFAULT_MAX_FAILURES)
+}
+
+/// [`validate_batch`] keeping the first `max_failures` failures.
+pub fn validate_batch_with_max_failures<'a>(
+    ids: impl Iterator<Item = &'a str>,
+    max_failures: usize,
+) -> ValidationReport<'a> {
+    let mut report = ValidationReport::empty();
+    for (index, id) in ids.enumerate() {
+        report.check(index, id, max_failures);
+    }
+    report
+}
+
+/// [`validate_batch_with_max_failures`] over a slice on the rayon thread pool. The
+/// report is the same as the serial one.
+#[cfg(feature = "rayon")]
+pub fn par_validate_batch<S: AsRef<str> + Sync>(
+    ids: &[S],
+    max_failures: usize,
+) -> ValidationReport<'_> {
+    use rayon::prelude::*;
+
+    ids.par_chunks(PARALLEL_CHUNK_SIZE)
+        .enumerate()
+        .map(|(chunk, ids)| {
+            let mut report = ValidationReport::empty();
+            for (offset, id) in ids.iter().enumerate() {
+                report.check(
+                    chunk * PARALLEL_CHUNK_SIZE + offset,
+                    id.as_ref(),
+                    max_failures,
+                );
+            }
+            report
+        })
+        .reduce(ValidationReport::empty, |a, b| a.merge(b, max_failures))
+}
+
+/// [`encode_column_into`] with a newline after each id, into a new buffer of exactly
+/// 26 bytes per id.
+pub fn encode_column(ids: &[Uuid]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_column_into(ids, &mut buf, b'\n');
+    buf
+}
+
+/// Appends the canonical encoding of each id followed by `sep` to `buf`, e.g. to export
+/// a CSV column, reserving the space once up front.
+pub fn encode_column_into(ids: &[Uuid], buf: &mut Vec<u8>, sep: u8) {
+    buf.reserve(ids.len() * (ENCODED_LEN + 1));
+    for id in ids {
+        buf.extend_from_slice(&id.encode());
+        buf.push(sep);
+    }
+}
+
+/// Parses ids separated, or terminated, by `sep`, as written by [`encode_column_into`].
+/// Fails with the index of the first record that doesn't parse; an empty record between
+/// two separators is an error too.
+pub fn decode_column(buf: &[u8], sep: u8) -> Result<Vec<Uuid>, (usize, UuidError)> {
+    let buf = buf.strip_suffix(&[sep]).unwrap_or(buf);
+    if buf.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut ids = Vec::with_capacity(buf.len() / ENCODED_LEN + 1);
+    for (index, record) in buf.split(|b| *b == sep).enumerate() {
+        ids.push(Uuid::from_ascii(record).map_err(|err| (index, err))?);
+    }
+    Ok(ids)
+}
@@ -0,0 +1,75 @@
+use crate::error::UuidError;
+use crate::uuid::Uuid;
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+use std::ffi::OsStr;
+
+/// The parser behind `clap::value_parser!(Uuid)`. Its errors name the argument, the
+/// offending character and its position, with a hint about characters that are easy to
+/// confuse. `Vec<Uuid>` arguments use it for every value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidValueParser;
+
+impl UuidValueParser {
+    pub fn new() -> Self {
+        UuidValueParser
+    }
+}
+
+impl ValueParserFactory for Uuid {
+    type Parser = UuidValueParser;
+
+    fn value_parser() -> Self::Parser {
+        UuidValueParser
+    }
+}
+
+impl TypedValueParser for UuidValueParser {
+    type Value = Uuid;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Uuid, Error> {
+        let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+        let Some(value) = value.to_str() else {
+            return Err(Error::raw(
+                ErrorKind::InvalidUtf8,
+                format!("invalid UTF-8 in the value for '{}'\n", arg),
+            )
+            .with_cmd(cmd));
+        };
+        Uuid::try_from(value).map_err(|err| {
+            let mut message = format!("invalid value '{}' for '{}': ", value, arg);
+            match &err {
+                UuidError::InvalidCharacter { position, .. } => {
+                    // the position of a non-ascii byte is the start of its character
+                    let c = value[*position..].chars().next().unwrap_or_default();
+                    message += &format!("invalid character {:?} at position {}", c, position);
+                }
+                err => message += &err.to_string(),
+            }
+            if let Some(hint) = hint(&err) {
+                message += &format!("\n\n  tip: {}", hint);
+            }
+            message.push('\n');
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        })
+    }
+}
+
+fn hint(err: &UuidError) -> Option<&'static str> {
+    match err {
+        UuidError::InvalidCharacter { byte: b'U' | b'u', position } if *position < 24 => {
+            Some("'U' is only valid as the final checksum character")
+        }
+        UuidError::InvalidCharacter { .. } => Some(
+            "ids use the digits and the letters A-Z except U, with I and L read as 1 and O as 0",
+        ),
+        UuidError::InvalidLength(_) => {
+            Some("an id is 24 characters followed by a checksum character")
+        }
+        UuidError::ChecksumMismatch => Some(
+            "a character was likely mistyped, check easily confused ones such as V and U, or 5 and S",
+        ),
+        _ => None,
+    }
+}
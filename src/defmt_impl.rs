@@ -0,0 +1,12 @@
+// only `core` paths, so the impl stays usable from no_std firmware
+#![deny(clippy::std_instead_of_core, clippy::std_instead_of_alloc)]
+
+use crate::encode::ENCODED_LEN;
+use crate::uuid::Uuid;
+
+/// The canonical 25 character encoding, written from a stack buffer.
+impl defmt::Format for Uuid {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=str}", self.encode_crockford(&mut [0; ENCODED_LEN]))
+    }
+}
@@ -0,0 +1,42 @@
+use crate::error::UuidError;
+use crate::uuid::Uuid;
+use aws_sdk_dynamodb::primitives::Blob;
+use aws_sdk_dynamodb::types::AttributeValue;
+use std::collections::HashMap;
+
+impl Uuid {
+    /// The 15 raw bytes as a `B` attribute.
+    pub fn to_binary_attr(&self) -> AttributeValue {
+        AttributeValue::B(Blob::new(self.to_byte_array().to_vec()))
+    }
+
+    /// A single attribute key map, `{name: S}`, e.g. for `get_item().set_key(...)`.
+    pub fn to_key(&self, name: &str) -> HashMap<String, AttributeValue> {
+        HashMap::from([(name.to_string(), AttributeValue::from(*self))])
+    }
+}
+
+/// The canonical string as an `S` attribute.
+impl From<Uuid> for AttributeValue {
+    fn from(uuid: Uuid) -> Self {
+        AttributeValue::S(uuid.to_string())
+    }
+}
+
+/// Reads an `S` attribute, verifying the checksum, or a 15 byte `B` attribute.
+impl TryFrom<&AttributeValue> for Uuid {
+    type Error = UuidError;
+
+    fn try_from(value: &AttributeValue) -> Result<Self, Self::Error> {
+        match value {
+            AttributeValue::S(s) => Uuid::try_from(s.as_str()),
+            AttributeValue::B(blob) => Uuid::from_slice(blob.as_ref()),
+            AttributeValue::N(_) => Err(UuidError::InvalidEncoding(
+                "numeric N attributes can't hold a uuid exactly, store it as S or B".to_string(),
+            )),
+            _ => Err(UuidError::InvalidEncoding(
+                "expected an S or B attribute".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Reed–Solomon error correction over GF(32), for ids etched or printed on surfaces that
+//! get damaged.
+
+use crate::encode::{CROCKFORD_CHARS, ENCODED_LEN};
+use crate::error::UuidError;
+use crate::parse::{BODY_VALUES, INVALID};
+use crate::uuid::Uuid;
+
+const DATA_LEN: usize = ENCODED_LEN - 1;
+const PARITY_LEN: usize = 4;
+// the codeword is the 24 body symbols followed by the parity symbols
+const CODE_LEN: usize = DATA_LEN + PARITY_LEN;
+const MAX_CORRECTIONS: usize = PARITY_LEN / 2;
+
+// powers of the primitive element of GF(32) modulo x^5 + x^2 + 1, repeated once so
+// that the sum of two logarithms can index it directly
+const GF_EXP: [u8; 62] = {
+    let mut table = [0u8; 62];
+    let mut value = 1u8;
+    let mut i = 0;
+    while i < 62 {
+        table[i] = value;
+        value <<= 1;
+        if value & 0x20 != 0 {
+            value ^= 0x25;
+        }
+        i += 1;
+    }
+    table
+};
+
+const GF_LOG: [u8; 32] = {
+    let mut table = [0u8; 32];
+    let mut i = 0;
+    while i < 31 {
+        table[GF_EXP[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    GF_EXP[GF_LOG[a as usize] as usize + GF_LOG[b as usize] as usize]
+}
+
+fn gf_inv(a: u8) -> u8 {
+    GF_EXP[31 - GF_LOG[a as usize] as usize]
+}
+
+fn gf_alpha(power: usize) -> u8 {
+    GF_EXP[power % 31]
+}
+
+// evaluates a polynomial stored lowest degree first
+fn poly_eval(poly: &[u8], x: u8) -> u8 {
+    poly.iter().rev().fold(0, |acc, c| gf_mul(acc, x) ^ c)
+}
+
+// g(x) = (x - 1)(x - a)(x - a^2)(x - a^3), highest degree first
+fn generator_poly() -> [u8; PARITY_LEN + 1] {
+    let mut poly = [0u8; PARITY_LEN + 1];
+    poly[0] = 1;
+    for i in 0..PARITY_LEN {
+        let root = gf_alpha(i);
+        for j in (1..=i + 1).rev() {
+            poly[j] ^= gf_mul(root, poly[j - 1]);
+        }
+    }
+    poly
+}
+
+fn parity(data: &[u8; DATA_LEN]) -> [u8; PARITY_LEN] {
+    let generator = generator_poly();
+    let mut remainder = [0u8; CODE_LEN];
+    remainder[..DATA_LEN].copy_from_slice(data);
+    for i in 0..DATA_LEN {
+        let coef = remainder[i];
+        if coef != 0 {
+            for (j, g) in generator.iter().enumerate().skip(1) {
+                remainder[i + j] ^= gf_mul(*g, coef);
+            }
+        }
+    }
+    let mut parity = [0u8; PARITY_LEN];
+    parity.copy_from_slice(&remainder[DATA_LEN..]);
+    parity
+}
+
+fn code_syndromes(code: &[u8; CODE_LEN]) -> [u8; PARITY_LEN] {
+    let mut syndromes = [0u8; PARITY_LEN];
+    for (i, syndrome) in syndromes.iter_mut().enumerate() {
+        let x = gf_alpha(i);
+        *syndrome = code.iter().fold(0, |acc, c| gf_mul(acc, x) ^ c);
+    }
+    syndromes
+}
+
+// corrects `code` in place, returning the number of corrected symbols, or `None` when
+// there are more errors than the code can correct
+fn correct(code: &mut [u8; CODE_LEN]) -> Option<usize> {
+    let syndromes = code_syndromes(code);
+    if syndromes.iter().all(|s| *s == 0) {
+        return Some(0);
+    }
+
+    // Berlekamp–Massey, for the error locator lowest degree first
+    let mut locator = [0u8; PARITY_LEN + 1];
+    let mut previous = [0u8; PARITY_LEN + 1];
+    locator[0] = 1;
+    previous[0] = 1;
+    let (mut errors, mut shift, mut last_discrepancy) = (0, 1, 1u8);
+    for n in 0..PARITY_LEN {
+        let discrepancy = (1..=errors).fold(syndromes[n], |acc, i| {
+            acc ^ gf_mul(locator[i], syndromes[n - i])
+        });
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let coef = gf_mul(discrepancy, gf_inv(last_discrepancy));
+        let before = locator;
+        for i in shift..=PARITY_LEN {
+            locator[i] ^= gf_mul(coef, previous[i - shift]);
+        }
+        if 2 * errors <= n {
+            errors = n + 1 - errors;
+            previous = before;
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+    if errors > MAX_CORRECTIONS {
+        return None;
+    }
+
+    // the error evaluator, syndromes times locator modulo x^4
+    let mut evaluator = [0u8; PARITY_LEN];
+    for (i, value) in evaluator.iter_mut().enumerate() {
+        *value = (0..=i).fold(0, |acc, j| acc ^ gf_mul(syndromes[j], locator[i - j]));
+    }
+    // the formal derivative keeps the odd powers
+    let mut derivative = [0u8; PARITY_LEN];
+    for i in (1..=PARITY_LEN).step_by(2) {
+        derivative[i - 1] = locator[i];
+    }
+
+    // Chien search and Forney's formula
+    let mut found = 0;
+    for (index, symbol) in code.iter_mut().enumerate() {
+        let position = gf_alpha(CODE_LEN - 1 - index);
+        let inverse = gf_inv(position);
+        if poly_eval(&locator, inverse) != 0 {
+            continue;
+        }
+        let denominator = poly_eval(&derivative, inverse);
+        if denominator == 0 {
+            return None;
+        }
+        let magnitude = gf_mul(
+            gf_mul(position, poly_eval(&evaluator, inverse)),
+            gf_inv(denominator),
+        );
+        *symbol ^= magnitude;
+        found += 1;
+    }
+    if found != errors || code_syndromes(code).iter().any(|s| *s != 0) {
+        return None;
+    }
+    Some(errors)
+}
+
+impl Uuid {
+    /// The 25 character encoding followed by 4 Reed–Solomon parity characters over
+    /// GF(32), in the Crockford alphabet: 29 characters that
+    /// [`Uuid::from_ecc_string`] can read back despite up to 2 damaged characters.
+    pub fn to_ecc_string(&self) -> String {
+        let encoded = self.encode();
+        let mut data = [0u8; DATA_LEN];
+        for (symbol, c) in data.iter_mut().zip(&encoded) {
+            *symbol = BODY_VALUES[*c as usize];
+        }
+        let mut ecc: String = encoded.iter().map(|b| *b as char).collect();
+        ecc.extend(
+            parity(&data)
+                .iter()
+                .map(|p| CROCKFORD_CHARS[*p as usize] as char),
+        );
+        ecc
+    }
+
+    /// Parses [`Uuid::to_ecc_string`], correcting errors, and returns the uuid with the
+    /// number of characters that were corrected.
+    ///
+    /// Up to 2 wrong or unreadable characters among the 24 body and 4 parity characters
+    /// are corrected, or a wrong checksum character on its own. The checksum character
+    /// then confirms the correction. More errors fail with
+    /// [`UuidError::InvalidEncoding`]; with 3 or more, a wrong id is very unlikely but,
+    /// as with any code of this size, possible.
+    pub fn from_ecc_string(s: &str) -> Result<(Self, usize), UuidError> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != ENCODED_LEN + PARITY_LEN {
+            return Err(UuidError::InvalidLength(chars.len()));
+        }
+        let symbols = chars[..DATA_LEN].iter().chain(&chars[ENCODED_LEN..]);
+        let mut code = [0u8; CODE_LEN];
+        for (symbol, c) in code.iter_mut().zip(symbols) {
+            // an unreadable character is just another error to correct
+            *symbol = match c.is_ascii() {
+                true => BODY_VALUES[*c as usize],
+                false => INVALID,
+            };
+            if *symbol == INVALID {
+                *symbol = 0;
+            }
+        }
+
+        let too_many = || UuidError::InvalidEncoding("too many errors to correct".to_string());
+        let mut corrected = correct(&mut code).ok_or_else(too_many)?;
+        let value = code[..DATA_LEN]
+            .iter()
+            .fold(0u128, |acc, symbol| (acc << 5) | *symbol as u128);
+        let uuid = Uuid::from_u128(value);
+        let check = Uuid::get_checksum_char(uuid.checksum);
+        if !chars[DATA_LEN].eq_ignore_ascii_case(&check) {
+            if corrected > 0 {
+                return Err(too_many());
+            }
+            corrected = 1;
+        }
+        Ok((uuid, corrected))
+    }
+}
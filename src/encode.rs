@@ -0,0 +1,1435 @@
+use crate::error::UuidError;
+#[cfg(feature = "encrypt")]
+use crate::generate::rng;
+use crate::uuid::{Bytes, Uuid, BYTE_SIZE, CHECKSUM_MODULO};
+#[cfg(feature = "graphql-cursor")]
+use base64::Engine;
+#[cfg(feature = "encrypt")]
+use ring::rand::SecureRandom;
+
+// we are trying to fit 8 bits bytes into a 5 bit char, plus one checksum char
+pub(crate) const ENCODED_LEN: usize = (BYTE_SIZE * 8 / 5) + 1;
+pub(crate) const CROCKFORD_CHARS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+pub(crate) const CROCKFORD_CHECKSUM_CHARS: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+/// The checksum character for each remainder, indexed by remainder.
+pub const CHECKSUM_ALPHABET: &str = CROCKFORD_CHECKSUM_CHARS;
+#[cfg(feature = "encrypt")]
+const NONCE_SIZE: usize = 12;
+#[cfg(feature = "encrypt")]
+const TRUNCATED_TAG_SIZE: usize = 4;
+#[cfg(feature = "encrypt")]
+pub const ENCRYPTED_SIZE: usize = NONCE_SIZE + BYTE_SIZE + TRUNCATED_TAG_SIZE;
+#[cfg(feature = "geohash")]
+const GEOHASH_CHARS: &str = "0123456789bcdefghjkmnpqrstuvwxyz";
+#[cfg(feature = "geohash")]
+const GEOHASH_MAX_LEN: usize = 12;
+const IDN_ACE_PREFIX: &str = "xn--";
+// International Morse for the crockford alphabet. The checksum symbols `*` and `~`
+// have no Morse sign of their own and borrow those of `+` and `-`.
+const MORSE_CODE: [(u8, &str); 40] = [
+    (b'0', "-----"),
+    (b'1', ".----"),
+    (b'2', "..---"),
+    (b'3', "...--"),
+    (b'4', "....-"),
+    (b'5', "....."),
+    (b'6', "-...."),
+    (b'7', "--..."),
+    (b'8', "---.."),
+    (b'9', "----."),
+    (b'A', ".-"),
+    (b'B', "-..."),
+    (b'C', "-.-."),
+    (b'D', "-.."),
+    (b'E', "."),
+    (b'F', "..-."),
+    (b'G', "--."),
+    (b'H', "...."),
+    (b'I', ".."),
+    (b'J', ".---"),
+    (b'K', "-.-"),
+    (b'L', ".-.."),
+    (b'M', "--"),
+    (b'N', "-."),
+    (b'O', "---"),
+    (b'P', ".--."),
+    (b'Q', "--.-"),
+    (b'R', ".-."),
+    (b'S', "..."),
+    (b'T', "-"),
+    (b'U', "..-"),
+    (b'V', "...-"),
+    (b'W', ".--"),
+    (b'X', "-..-"),
+    (b'Y', "-.--"),
+    (b'Z', "--.."),
+    (b'*', ".-.-."),
+    (b'~', "-....-"),
+    (b'$', "...-..-"),
+    (b'=', "-...-"),
+];
+// ceil(120 / 11)
+const MNEMONIC_WORDS: usize = 11;
+const MATRIX_COLUMNS: usize = 5;
+// two numbers of 60 bits, each at most 11 characters of the default alphabet
+#[cfg(feature = "sqids")]
+const SQIDS_CHUNK_BITS: u32 = 60;
+#[cfg(feature = "sqids")]
+const SQIDS_CHUNKS: u32 = (BYTE_SIZE as u32 * 8) / SQIDS_CHUNK_BITS;
+#[cfg(feature = "proquint")]
+const PROQUINT_CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+#[cfg(feature = "proquint")]
+const PROQUINT_VOWELS: &[u8; 4] = b"aiou";
+// 120 bits zero-extended to 8 quintets of 16 bits
+#[cfg(feature = "proquint")]
+const PROQUINT_WORDS: usize = 8;
+
+// CBOR tag 37 (a one byte argument, 0xd8 0x25) then a byte string of 15 bytes (0x4f)
+#[cfg(feature = "cbor-tag")]
+const CBOR_UUID_HEADER: [u8; 3] = [0xd8, 0x25, 0x40 | BYTE_SIZE as u8];
+
+#[cfg(feature = "graphql-cursor")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GraphqlCursor {
+    #[serde(rename = "type")]
+    entity_type: String,
+    id: String,
+}
+
+// parses a flat JSON object whose values are strings or unsigned integers, the latter
+// kept as their digits, which is all a cursor or a compact id holds
+fn parse_json_flat_object(input: &str) -> Option<Vec<(String, String)>> {
+    fn skip_ws(chars: &mut std::iter::Peekable<std::str::Chars>) {
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+    }
+    fn string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+        skip_ws(chars);
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut out = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(out),
+                '\\' => match chars.next()? {
+                    '"' => out.push('"'),
+                    '\\' => out.push('\\'),
+                    '/' => out.push('/'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'u' => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        let code = u32::from_str_radix(&hex, 16).ok()?;
+                        // surrogate pairs never appear in cursors we produce
+                        out.push(char::from_u32(code)?);
+                    }
+                    _ => return None,
+                },
+                c if (c as u32) < 0x20 => return None,
+                c => out.push(c),
+            }
+        }
+    }
+
+    let mut chars = input.chars().peekable();
+    let mut fields = Vec::new();
+    skip_ws(&mut chars);
+    if chars.next()? != '{' {
+        return None;
+    }
+    skip_ws(&mut chars);
+    if chars.next_if_eq(&'}').is_none() {
+        loop {
+            let key = string(&mut chars)?;
+            skip_ws(&mut chars);
+            if chars.next()? != ':' {
+                return None;
+            }
+            skip_ws(&mut chars);
+            let value = if chars.peek()?.is_ascii_digit() {
+                std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect()
+            } else {
+                string(&mut chars)?
+            };
+            fields.push((key, value));
+            skip_ws(&mut chars);
+            match chars.next()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+    skip_ws(&mut chars);
+    chars.next().is_none().then_some(fields)
+}
+
+/// The bases supported by [`Uuid::to_multibase`], each identified by the
+/// [multibase](https://github.com/multiformats/multibase) prefix character.
+#[cfg(feature = "multibase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multibase {
+    /// RFC 4648 base32, lower case and unpadded, prefix `b`.
+    Base32Lower,
+    /// RFC 4648 base32, upper case and unpadded, prefix `B`.
+    Base32Upper,
+    /// Bitcoin base58, prefix `z`.
+    Base58Btc,
+    /// RFC 4648 url safe base64, unpadded, prefix `u`.
+    Base64Url,
+}
+
+#[cfg(feature = "multibase")]
+impl Multibase {
+    pub fn prefix(self) -> char {
+        self.base().code()
+    }
+
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            'b' => Some(Multibase::Base32Lower),
+            'B' => Some(Multibase::Base32Upper),
+            'z' => Some(Multibase::Base58Btc),
+            'u' => Some(Multibase::Base64Url),
+            _ => None,
+        }
+    }
+
+    fn base(self) -> multibase::Base {
+        match self {
+            Multibase::Base32Lower => multibase::Base::Base32Lower,
+            Multibase::Base32Upper => multibase::Base::Base32Upper,
+            Multibase::Base58Btc => multibase::Base::Base58Btc,
+            Multibase::Base64Url => multibase::Base::Base64Url,
+        }
+    }
+}
+
+// the characters of a custom checksum alphabet, which must be distinct and more
+// numerous than those of the standard one
+pub(crate) fn custom_checksum_alphabet(alphabet: &str) -> Result<Vec<char>, UuidError> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.len() <= CHECKSUM_MODULO {
+        return Err(UuidError::InvalidConfiguration(format!(
+            "checksum alphabet needs more than {} characters, got {}",
+            CHECKSUM_MODULO,
+            chars.len()
+        )));
+    }
+    if let Some(c) = chars
+        .iter()
+        .enumerate()
+        .find_map(|(i, c)| chars[..i].contains(c).then_some(c))
+    {
+        return Err(UuidError::InvalidConfiguration(format!(
+            "checksum alphabet repeats {:?}",
+            c
+        )));
+    }
+    Ok(chars)
+}
+
+pub(crate) fn custom_checksum_char(value: u128, alphabet: &[char]) -> char {
+    alphabet[(value % alphabet.len() as u128) as usize]
+}
+
+impl Uuid {
+    /// Embeds a geohash in a location stamped uuid.
+    ///
+    /// The geohash is lowercased and stored as ASCII in bytes 0-11, zero padded; longer
+    /// geohashes are truncated to 12 characters (about 3.7cm precision). Bytes 12-14 are
+    /// zero.
+    #[cfg(feature = "geohash")]
+    pub fn from_geohash(geohash: &str) -> Result<Self, UuidError> {
+        let geohash = geohash.to_ascii_lowercase();
+        if let Some(position) = geohash
+            .bytes()
+            .position(|b| !GEOHASH_CHARS.as_bytes().contains(&b))
+        {
+            let byte = geohash.as_bytes()[position];
+            return Err(UuidError::InvalidCharacter { position, byte });
+        }
+
+        let geohash = &geohash.as_bytes()[..geohash.len().min(GEOHASH_MAX_LEN)];
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes[..geohash.len()].copy_from_slice(geohash);
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// Reads bytes 0-11 as the geohash stored by [`Uuid::from_geohash`], without the
+    /// zero padding.
+    #[cfg(feature = "geohash")]
+    pub fn to_geohash_prefix(&self) -> String {
+        let prefix = &self.bytes.as_slice()[..GEOHASH_MAX_LEN];
+        String::from_utf8_lossy(prefix)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+
+    /// Splits the uuid into `n` shares using Shamir's secret sharing over GF(256),
+    /// any `k` of which reconstruct it with [`Uuid::reconstruct_from_shares`].
+    ///
+    /// Each share is `[k, index, y_0, .., y_14]`: the threshold and the 1 based share
+    /// index are embedded so that too few shares are rejected instead of silently
+    /// reconstructing a different uuid.
+    #[cfg(feature = "secret-sharing")]
+    pub fn split_into_shares(&self, n: u8, k: u8) -> Result<Vec<Vec<u8>>, UuidError> {
+        if k == 0 || k > n {
+            return Err(UuidError::InvalidShares(format!(
+                "threshold {} must be between 1 and {}",
+                k, n
+            )));
+        }
+
+        // sharks shares are the index followed by one evaluated byte per secret byte
+        let shares = sharks::Sharks(k)
+            .dealer(self.bytes.as_slice())
+            .take(n as usize)
+            .map(|share| {
+                let mut bytes = Vec::with_capacity(BYTE_SIZE + 2);
+                bytes.push(k);
+                bytes.extend(Vec::from(&share));
+                bytes
+            })
+            .collect();
+        Ok(shares)
+    }
+
+    /// Reconstructs a uuid from at least `k` shares produced by [`Uuid::split_into_shares`].
+    #[cfg(feature = "secret-sharing")]
+    pub fn reconstruct_from_shares(shares: &[Vec<u8>]) -> Result<Self, UuidError> {
+        let invalid = |reason: &str| UuidError::InvalidShares(reason.to_string());
+
+        if shares.iter().any(|share| share.len() != BYTE_SIZE + 2) {
+            return Err(invalid("shares are not from the same split"));
+        }
+        let threshold = match shares.first() {
+            Some(share) => share[0] as usize,
+            None => return Err(invalid("no shares")),
+        };
+        if shares.iter().any(|share| share[0] as usize != threshold) {
+            return Err(invalid("shares are not from the same split"));
+        }
+        if shares.len() < threshold {
+            return Err(UuidError::InvalidShares(format!(
+                "{} shares are needed, got {}",
+                threshold,
+                shares.len()
+            )));
+        }
+
+        let shares = &shares[..threshold];
+        let xs: Vec<u8> = shares.iter().map(|share| share[1]).collect();
+        if xs
+            .iter()
+            .enumerate()
+            .any(|(i, x)| *x == 0 || xs[..i].contains(x))
+        {
+            return Err(invalid("duplicate or invalid share index"));
+        }
+
+        let shares = shares
+            .iter()
+            .map(|share| sharks::Share::try_from(&share[1..]).map_err(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let secret = sharks::Sharks(threshold as u8)
+            .recover(&shares)
+            .map_err(invalid)?;
+
+        let bytes = Bytes::try_from(secret)?;
+        let checksum = bytes.derive_crockford_checksum();
+        Ok(Self { bytes, checksum })
+    }
+
+    // AES-256-GCM over `in_out` returning the full 16 byte tag
+    #[cfg(feature = "encrypt")]
+    fn seal(
+        key: &[u8; 32],
+        nonce: [u8; NONCE_SIZE],
+        in_out: &mut [u8],
+    ) -> Result<ring::aead::Tag, ring::error::Unspecified> {
+        use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM};
+
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key)?);
+        let nonce = Nonce::assume_unique_for_key(nonce);
+        key.seal_in_place_separate_tag(nonce, Aad::empty(), in_out)
+    }
+
+    /// Encrypts the uuid with AES-256-GCM as `nonce (12) || ciphertext (15) || tag (4)`.
+    ///
+    /// The nonce is random so encrypting the same uuid twice gives different outputs.
+    /// The tag is truncated to 4 bytes to keep the output small, which means a forgery
+    /// succeeds with probability 2^-32: this hides the uuid in untrusted storage but
+    /// must not be the only authentication of a value an attacker can submit repeatedly.
+    #[cfg(feature = "encrypt")]
+    pub fn encrypt(&self, key: &[u8; 32]) -> Result<[u8; ENCRYPTED_SIZE], UuidError> {
+        let mut nonce = [0u8; NONCE_SIZE];
+        rng()
+            .fill(&mut nonce)
+            .map_err(|_| UuidError::RngExhausted)?;
+
+        let mut encrypted = [0u8; ENCRYPTED_SIZE];
+        let (head, tail) = encrypted.split_at_mut(NONCE_SIZE);
+        let (ciphertext, truncated_tag) = tail.split_at_mut(BYTE_SIZE);
+        head.copy_from_slice(&nonce);
+        ciphertext.copy_from_slice(self.bytes.as_slice());
+
+        let tag = Uuid::seal(key, nonce, ciphertext).map_err(|_| UuidError::DecryptionFailed)?;
+        truncated_tag.copy_from_slice(&tag.as_ref()[..TRUNCATED_TAG_SIZE]);
+        Ok(encrypted)
+    }
+
+    /// Decrypts the output of [`Uuid::encrypt`], failing with
+    /// [`UuidError::DecryptionFailed`] on a wrong key or tampered input.
+    #[cfg(feature = "encrypt")]
+    pub fn decrypt(ciphertext: &[u8; ENCRYPTED_SIZE], key: &[u8; 32]) -> Result<Self, UuidError> {
+        let (nonce, tail) = ciphertext.split_at(NONCE_SIZE);
+        let (ciphertext, truncated_tag) = tail.split_at(BYTE_SIZE);
+        let nonce: [u8; NONCE_SIZE] = nonce.try_into().unwrap();
+
+        // ring only opens full tags, so recover the plaintext from the CTR keystream
+        // and verify the truncated tag by sealing it again
+        let mut plaintext = [0u8; BYTE_SIZE];
+        let _ = Uuid::seal(key, nonce, &mut plaintext).map_err(|_| UuidError::DecryptionFailed)?;
+        plaintext
+            .iter_mut()
+            .zip(ciphertext)
+            .for_each(|(keystream, c)| *keystream ^= c);
+
+        let mut resealed = plaintext;
+        let tag = Uuid::seal(key, nonce, &mut resealed).map_err(|_| UuidError::DecryptionFailed)?;
+        ring::constant_time::verify_slices_are_equal(
+            &tag.as_ref()[..TRUNCATED_TAG_SIZE],
+            truncated_tag,
+        )
+        .map_err(|_| UuidError::DecryptionFailed)?;
+
+        let bytes = Bytes::try_from(plaintext.to_vec())?;
+        let checksum = bytes.derive_crockford_checksum();
+        Ok(Self { bytes, checksum })
+    }
+
+    /// The crockford base32 encoding of the uuid, without the checksum character.
+    pub fn to_crockford_base32(&self) -> String {
+        base32::encode(base32::Alphabet::Crockford, self.bytes.as_slice())
+    }
+
+    #[deprecated(since = "0.2.0", note = "use `to_crockford_base32` instead")]
+    pub fn value(&self) -> String {
+        self.to_crockford_base32()
+    }
+
+    pub(crate) fn get_checksum_char(checksum: u8) -> char {
+        CROCKFORD_CHECKSUM_CHARS
+            .chars()
+            .nth(checksum as usize)
+            .unwrap()
+    }
+
+    /// The crockford base32 encoding followed by the checksum character, the same as
+    /// the `Display` form.
+    pub fn to_crockford_base32_with_checksum(&self) -> String {
+        format!(
+            "{}{}",
+            self.to_crockford_base32(),
+            Uuid::get_checksum_char(self.checksum)
+        )
+    }
+
+    /// The encoding with the checksum character shifted `rotation` places along the
+    /// checksum alphabet.
+    ///
+    /// This is obfuscation, not security: anyone who knows the scheme recovers the
+    /// rotation from a single id by trying all 37. It only stops ids from being
+    /// accepted, or guessed, by systems that weren't told the rotation.
+    pub fn display_with_rotated_alphabet(&self, rotation: usize) -> String {
+        let mut encoded = self.encode();
+        let index = (self.checksum as usize + rotation) % CHECKSUM_MODULO;
+        encoded[ENCODED_LEN - 1] = CROCKFORD_CHECKSUM_CHARS.as_bytes()[index];
+        encoded.iter().map(|b| *b as char).collect()
+    }
+
+    /// The encoding with the checksum computed over a custom `alphabet`: the checksum
+    /// character is `alphabet[value % n]` for an alphabet of `n` distinct characters,
+    /// more than the 37 of the standard one.
+    ///
+    /// Such strings are incompatible with the standard alphabet: they fail to parse
+    /// with a checksum mismatch, or worse, parse by chance, and can only be checked
+    /// with [`Uuid::is_valid_with_custom_alphabet`].
+    pub fn to_string_with_checksum_alphabet(&self, alphabet: &str) -> Result<String, UuidError> {
+        let alphabet = custom_checksum_alphabet(alphabet)?;
+        let mut encoded: String = self.encode()[..ENCODED_LEN - 1]
+            .iter()
+            .map(|b| *b as char)
+            .collect();
+        encoded.push(custom_checksum_char(self.to_u128(), &alphabet));
+        Ok(encoded)
+    }
+
+    /// Generates a uuid to encode with [`Uuid::to_string_with_checksum_alphabet`],
+    /// failing like it if `alphabet` isn't usable. The uuid doesn't record the alphabet.
+    pub fn new_with_custom_checksum_alphabet(alphabet: &str) -> Result<Self, UuidError> {
+        custom_checksum_alphabet(alphabet)?;
+        Ok(Uuid::new())
+    }
+
+    /// The parameters of the checksum character.
+    pub fn checksum_algorithm() -> ChecksumInfo {
+        ChecksumInfo {
+            modulo: CHECKSUM_MODULO,
+            alphabet: CHECKSUM_ALPHABET,
+            alphabet_len: CHECKSUM_ALPHABET.len(),
+        }
+    }
+
+    /// Number of characters in [`Uuid::to_crockford_base32`].
+    pub fn encoded_length() -> usize {
+        ENCODED_LEN - 1
+    }
+
+    /// Number of characters in [`Uuid::to_crockford_base32_with_checksum`].
+    pub fn encoded_length_with_checksum() -> usize {
+        ENCODED_LEN
+    }
+
+    // the canonical upper case encoding, without allocating
+    pub(crate) fn encode(&self) -> [u8; ENCODED_LEN] {
+        let mut value = [0u8; 16];
+        let bytes = self.bytes.as_slice();
+        value[16 - bytes.len()..].copy_from_slice(bytes);
+        let value = u128::from_be_bytes(value);
+
+        let mut encoded = [0u8; ENCODED_LEN];
+        for (i, c) in encoded[..ENCODED_LEN - 1].iter_mut().enumerate() {
+            let shift = 5 * (ENCODED_LEN - 2 - i);
+            *c = CROCKFORD_CHARS[((value >> shift) & 0x1f) as usize];
+        }
+        encoded[ENCODED_LEN - 1] = Uuid::get_checksum_char(self.checksum) as u8;
+        encoded
+    }
+
+    /// Writes the canonical 25 character encoding into `buf` and returns it as a `&str`,
+    /// for formatting without a heap allocation, e.g. in embedded loggers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is shorter than [`Uuid::encoded_length_with_checksum`].
+    pub fn encode_crockford<'a>(&self, buf: &'a mut [u8]) -> &'a str {
+        let buf = &mut buf[..ENCODED_LEN];
+        buf.copy_from_slice(&self.encode());
+        std::str::from_utf8(buf).expect("crockford characters are ascii")
+    }
+
+    /// The encoding as a stack allocated, `Copy` string.
+    pub fn to_string_stack(&self) -> UuidString {
+        UuidString(self.encode())
+    }
+
+    /// Whether the first `prefix_chars` characters of both encodings match. Lengths past
+    /// the 25 encoded characters compare the whole encoding.
+    pub fn compare_prefix(&self, other: &Uuid, prefix_chars: usize) -> bool {
+        let len = prefix_chars.min(ENCODED_LEN);
+        // the canonical encoding is upper case, so this is already case-insensitive
+        self.encode()[..len] == other.encode()[..len]
+    }
+
+    /// The number of character positions, checksum included, at which the encodings of
+    /// `a` and `b` differ.
+    pub fn diff_count(a: &Uuid, b: &Uuid) -> usize {
+        let (a, b) = (a.encode(), b.encode());
+        a.iter().zip(&b).filter(|(a, b)| a != b).count()
+    }
+
+    /// A one line description of how the encoding changed from `a` to `b`, for audit
+    /// logs, e.g.
+    /// `Changed at positions [3, 24]: '4S0Y...' -> '4S0Z...' (3: 'Y' -> 'Z', 24: '6' -> '5')`,
+    /// or `Identical`. Positions count from 0 and include the checksum character.
+    pub fn diff_summary(a: &Uuid, b: &Uuid) -> String {
+        let (before, after) = (a.encode(), b.encode());
+        let positions: Vec<usize> = (0..ENCODED_LEN)
+            .filter(|i| before[*i] != after[*i])
+            .collect();
+        if positions.is_empty() {
+            return "Identical".to_string();
+        }
+        let changes: Vec<String> = positions
+            .iter()
+            .map(|i| format!("{}: '{}' -> '{}'", i, before[*i] as char, after[*i] as char))
+            .collect();
+        format!(
+            "Changed at positions {:?}: '{}' -> '{}' ({})",
+            positions,
+            a,
+            b,
+            changes.join(", ")
+        )
+    }
+
+    /// [`Uuid::compare_prefix`] under the name used by sharded stores.
+    pub fn share_shard(&self, other: &Uuid, shard_prefix_len: usize) -> bool {
+        self.compare_prefix(other, shard_prefix_len)
+    }
+
+    /// The first `prefix_len` characters of the encoding, e.g. to route to a shard.
+    pub fn shard_key(&self, prefix_len: usize) -> String {
+        let encoded = self.encode();
+        encoded[..prefix_len.min(ENCODED_LEN)]
+            .iter()
+            .map(|b| *b as char)
+            .collect()
+    }
+
+    /// The encoding split into chunks of `chunk_size` characters, the last one possibly
+    /// shorter, e.g. for printing. Sizes of 25 or more give the whole encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn to_chunks(&self, chunk_size: usize) -> Vec<String> {
+        self.encode()
+            .chunks(chunk_size)
+            .map(|chunk| chunk.iter().map(|b| *b as char).collect())
+            .collect()
+    }
+
+    /// Parses the concatenation of `chunks`, e.g. from [`Uuid::to_chunks`].
+    pub fn from_chunks(chunks: &[&str]) -> Result<Self, UuidError> {
+        Uuid::try_from(chunks.concat())
+    }
+
+    /// Returns the top 63 bits of the uuid as a non-negative Snowflake-style `i64`.
+    ///
+    /// This is lossy: only the leading 63 of the 120 bits are kept, so two
+    /// uuids that share those bits map to the same snowflake.
+    pub fn to_snowflake_i64(&self) -> i64 {
+        let mut high = [0u8; 8];
+        high.copy_from_slice(&self.bytes.as_slice()[..8]);
+        (u64::from_be_bytes(high) >> 1) as i64
+    }
+
+    /// Builds a uuid whose top 63 bits are the given snowflake, with the
+    /// remaining 57 bits zeroed. The sign bit of `id` is masked off.
+    ///
+    /// `Uuid::from_snowflake_i64(id).to_snowflake_i64() == id & i64::MAX` always holds,
+    /// but converting an arbitrary uuid to a snowflake and back does not round trip.
+    pub fn from_snowflake_i64(id: i64) -> Self {
+        let high = ((id & i64::MAX) as u64) << 1;
+        let mut bytes = vec![0u8; BYTE_SIZE];
+        bytes[..8].copy_from_slice(&high.to_be_bytes());
+        let bytes = Bytes::try_from(bytes).expect("snowflake bytes are always valid");
+        let checksum = bytes.derive_crockford_checksum();
+        Self { bytes, checksum }
+    }
+
+    /// Reads the 41 bit timestamp (bits 63-22) of the snowflake form.
+    ///
+    /// Following Snowflake convention the value is in milliseconds relative to the
+    /// generator's epoch (1288834974657 for Twitter), not the unix epoch.
+    pub fn snowflake_timestamp_ms(&self) -> u64 {
+        (self.to_snowflake_i64() >> 22) as u64
+    }
+
+    /// Builds a uuid from a 128 bit flake id, e.g. an extended Sonyflake or Snowflake id,
+    /// keeping its top 120 bits: the id is written big-endian into 16 bytes and the last
+    /// byte dropped.
+    ///
+    /// The low 8 bits don't fit, so ids that differ only there would collide. This fails
+    /// with [`UuidError::InvalidEncoding`] unless they are zero; use
+    /// [`Uuid::from_u128_id_lossy`] to drop them silently.
+    pub fn from_u128_id(val: u128) -> Result<Self, UuidError> {
+        if val as u8 != 0 {
+            return Err(UuidError::InvalidEncoding(
+                "the low 8 bits of a 128 bit id can't be kept".to_string(),
+            ));
+        }
+        Ok(Uuid::from_u128_id_lossy(val))
+    }
+
+    /// [`Uuid::from_u128_id`], discarding the low 8 bits of `val` whatever they are.
+    pub fn from_u128_id_lossy(val: u128) -> Self {
+        Uuid::from_u128(val >> 8)
+    }
+
+    /// The 128 bit flake id of [`Uuid::from_u128_id`]: the 15 bytes followed by a zero
+    /// byte, so `Uuid::from_u128_id_lossy(x).to_u128_id() == x >> 8 << 8`.
+    pub fn to_u128_id(&self) -> u128 {
+        self.to_u128() << 8
+    }
+
+    /// A quoted PostgreSQL `uuid` literal, e.g. `'002641e1-6fe7-cbc9-b846-bfafb5f4c377'`.
+    ///
+    /// The 15 bytes are left-padded with a zero byte to the 16 bytes of an RFC 4122 uuid,
+    /// so the first two hex digits are always `00`.
+    pub fn to_postgres_uuid_literal(&self) -> String {
+        let mut padded = [0u8; 16];
+        padded[1..].copy_from_slice(self.bytes.as_slice());
+        let hex: String = padded.iter().map(|b| format!("{:02x}", b)).collect();
+        format!(
+            "'{}-{}-{}-{}-{}'",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+
+    /// Parses a literal written by [`Uuid::to_postgres_uuid_literal`], with or without
+    /// the surrounding quotes. The padding byte must be zero.
+    pub fn from_postgres_uuid_literal(s: &str) -> Result<Self, UuidError> {
+        let invalid = |reason: &str| UuidError::InvalidEncoding(reason.to_string());
+        let s = s
+            .strip_prefix('\'')
+            .and_then(|s| s.strip_suffix('\''))
+            .unwrap_or(s);
+        let bytes = s.as_bytes();
+        if bytes.len() != 36 || [8, 13, 18, 23].iter().any(|i| bytes[*i] != b'-') {
+            return Err(invalid("not an 8-4-4-4-12 uuid literal"));
+        }
+
+        let hex: Vec<u8> = bytes.iter().copied().filter(|b| *b != b'-').collect();
+        if !hex.iter().all(u8::is_ascii_hexdigit) {
+            return Err(invalid("invalid hex digit"));
+        }
+        let mut padded = [0u8; 16];
+        for (byte, pair) in padded.iter_mut().zip(hex.chunks(2)) {
+            // validated ascii hex digits, neither conversion can fail
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+        }
+        if padded[0] != 0 {
+            return Err(invalid("padding byte is not zero"));
+        }
+        Ok(Uuid::from_array(padded[1..].try_into().unwrap()))
+    }
+
+    /// Wraps the uuid in an XML element, e.g. `<id>4S0Y2VZ7SF4VGHNZNYTZ9GVQ6</id>`.
+    pub fn to_xml_element(&self, tag_name: &str) -> String {
+        format!("<{tag_name}>{}</{tag_name}>", self)
+    }
+
+    /// Formats the uuid as an XML attribute, e.g. `id="4S0Y2VZ7SF4VGHNZNYTZ9GVQ6"`.
+    pub fn to_xml_attribute(&self, attr_name: &str) -> String {
+        format!("{attr_name}=\"{}\"", self)
+    }
+
+    /// Extracts the uuid from the first `tag_name` element in `xml`, ignoring whitespace
+    /// around the id and any attributes on the opening tag. This is a plain text search,
+    /// not an XML parser: comments, CDATA and nested elements are not understood.
+    pub fn from_xml_element(xml: &str, tag_name: &str) -> Result<Self, UuidError> {
+        let invalid = || UuidError::InvalidEncoding(format!("no <{}> element", tag_name));
+        let open = format!("<{}", tag_name);
+        let mut search = xml;
+        let content = loop {
+            let start = search.find(&open).ok_or_else(invalid)? + open.len();
+            let rest = &search[start..];
+            // `<idx>` or `<id-x>` must not match `<id>`
+            match rest.chars().next() {
+                Some('>') => break &rest[1..],
+                Some(c) if c.is_whitespace() => {
+                    let end = rest.find('>').ok_or_else(invalid)?;
+                    break &rest[end + 1..];
+                }
+                _ => search = rest,
+            }
+        };
+        let end = content
+            .find(&format!("</{}>", tag_name))
+            .ok_or_else(invalid)?;
+        Uuid::try_from(content[..end].trim())
+    }
+
+    /// The proquint encoding: the uuid as a 128 bit integer with a zero high byte, split
+    /// into 8 pronounceable 16 bit quintets, e.g.
+    /// `babab-babab-babab-babab-babab-babab-lusab-babad`.
+    ///
+    /// Each quintet is consonant-vowel-consonant-vowel-consonant, per the reference
+    /// algorithm at <https://arxiv.org/html/0901.4016>. The first quintet always starts
+    /// with `ba`, as it only holds 8 bits.
+    #[cfg(feature = "proquint")]
+    pub fn to_proquint(&self) -> String {
+        let value = self.to_u128();
+        (0..PROQUINT_WORDS)
+            .map(|i| {
+                let word = (value >> (16 * (PROQUINT_WORDS - 1 - i))) as u16 as usize;
+                [
+                    PROQUINT_CONSONANTS[word >> 12 & 0xf],
+                    PROQUINT_VOWELS[word >> 10 & 0x3],
+                    PROQUINT_CONSONANTS[word >> 6 & 0xf],
+                    PROQUINT_VOWELS[word >> 4 & 0x3],
+                    PROQUINT_CONSONANTS[word & 0xf],
+                ]
+                .iter()
+                .map(|b| *b as char)
+                .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Parses [`Uuid::to_proquint`], case-insensitive, with or without the hyphens.
+    #[cfg(feature = "proquint")]
+    pub fn from_proquint(s: &str) -> Result<Self, UuidError> {
+        let letters: Vec<u8> = s
+            .bytes()
+            .filter(|b| *b != b'-')
+            .map(|b| b.to_ascii_lowercase())
+            .collect();
+        if letters.len() != PROQUINT_WORDS * 5 {
+            return Err(UuidError::InvalidLength(letters.len()));
+        }
+
+        let mut value = 0u128;
+        for (i, letter) in letters.iter().enumerate() {
+            let (alphabet, bits): (&[u8], u32) = if i % 5 % 2 == 0 {
+                (PROQUINT_CONSONANTS, 4)
+            } else {
+                (PROQUINT_VOWELS, 2)
+            };
+            let digit = alphabet.iter().position(|c| c == letter).ok_or_else(|| {
+                UuidError::InvalidEncoding(format!(
+                    "{:?} is not a proquint {}",
+                    *letter as char,
+                    if bits == 4 { "consonant" } else { "vowel" }
+                ))
+            })?;
+            value = (value << bits) | digit as u128;
+        }
+        if value >> (BYTE_SIZE * 8) != 0 {
+            return Err(UuidError::InvalidEncoding(
+                "proquint exceeds 120 bits, the first quintet must start with ba".to_string(),
+            ));
+        }
+        Ok(Uuid::from_u128(value))
+    }
+
+    /// The 15 bytes as 3 rows of 5 upper case hex pairs, e.g.
+    /// `|AB CD EF 12 34|\n|56 78 9A BC DE|\n|F0 11 22 33 44|`, for debugging tools that
+    /// show bytes as a matrix.
+    pub fn to_matrix_notation(&self) -> String {
+        self.matrix_rows(false)
+    }
+
+    /// [`Uuid::to_matrix_notation`] with the XOR of each row's bytes as an extra column,
+    /// e.g. `|AB CD EF 12 34|AF|`.
+    pub fn to_matrix_with_row_checksums(&self) -> String {
+        self.matrix_rows(true)
+    }
+
+    fn matrix_rows(&self, row_checksums: bool) -> String {
+        self.bytes
+            .as_slice()
+            .chunks(MATRIX_COLUMNS)
+            .map(|row| {
+                let hex: Vec<String> = row.iter().map(|b| format!("{:02X}", b)).collect();
+                let mut line = format!("|{}|", hex.join(" "));
+                if row_checksums {
+                    let xor = row.iter().fold(0, |acc, b| acc ^ b);
+                    line += &format!("{:02X}|", xor);
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Each byte as 3 zero-padded decimal digits, `000` to `255`, e.g. for legacy
+    /// telecom systems: 45 digits in all.
+    pub fn to_bcd_string(&self) -> String {
+        self.to_bcd_string_with_separator("")
+    }
+
+    /// [`Uuid::to_bcd_string`] with `separator` between the 15 groups, e.g.
+    /// `000-038-065-...`.
+    pub fn to_bcd_string_with_separator(&self, separator: &str) -> String {
+        self.bytes
+            .as_slice()
+            .iter()
+            .map(|byte| format!("{:03}", byte))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Parses 45 decimal digits, or 15 groups of 3 digits separated by any non-digit
+    /// characters, each group at most `255`.
+    pub fn from_bcd_string(s: &str) -> Result<Self, UuidError> {
+        let groups: Vec<&str> = if s.bytes().all(|b| b.is_ascii_digit()) {
+            if s.len() != BYTE_SIZE * 3 {
+                return Err(UuidError::InvalidLength(s.len()));
+            }
+            (0..BYTE_SIZE).map(|i| &s[i * 3..i * 3 + 3]).collect()
+        } else {
+            s.split(|c: char| !c.is_ascii_digit())
+                .filter(|group| !group.is_empty())
+                .collect()
+        };
+        if groups.len() != BYTE_SIZE {
+            return Err(UuidError::InvalidEncoding(format!(
+                "expected {} groups of decimal digits, got {}",
+                BYTE_SIZE,
+                groups.len()
+            )));
+        }
+
+        let mut bytes = [0u8; BYTE_SIZE];
+        for (byte, group) in bytes.iter_mut().zip(groups) {
+            if group.len() != 3 {
+                return Err(UuidError::InvalidEncoding(format!(
+                    "decimal group {:?} is not 3 digits",
+                    group
+                )));
+            }
+            *byte = group.parse().map_err(|_| {
+                UuidError::InvalidEncoding(format!("decimal group {} exceeds 255", group))
+            })?;
+        }
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// Each of the 25 characters in Morse code, separated by ` | `, e.g.
+    /// `....- | ... | ----- | ...`.
+    pub fn to_morse_code(&self) -> String {
+        self.encode()
+            .iter()
+            .map(|c| {
+                MORSE_CODE
+                    .iter()
+                    .find(|(ch, _)| ch == c)
+                    .expect("every crockford character has a morse sign")
+                    .1
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Parses the output of [`Uuid::to_morse_code`], verifying the checksum.
+    pub fn from_morse_code(s: &str) -> Result<Self, UuidError> {
+        let encoded = s
+            .split('|')
+            .map(|sign| {
+                let sign = sign.trim();
+                MORSE_CODE
+                    .iter()
+                    .find(|(_, code)| *code == sign)
+                    .map(|(ch, _)| *ch)
+                    .ok_or_else(|| {
+                        UuidError::InvalidEncoding(format!("unknown morse sign {:?}", sign))
+                    })
+            })
+            .collect::<Result<Vec<u8>, UuidError>>()?;
+        Uuid::from_ascii(&encoded)
+    }
+
+    /// The lower case encoding behind the IDN ACE prefix, e.g.
+    /// `xn--4s0y2vz7sf4vghnznytz9gvq6`.
+    ///
+    /// This is not Punycode: the encoding is plain ASCII, whose Punycode form would be
+    /// itself, so the prefix is only added for uniformity with real IDN labels. Strict
+    /// IDNA validators reject such labels, and a checksum of `*`, `~`, `$` or `=` is
+    /// not a valid hostname character.
+    pub fn to_idn_label(&self) -> String {
+        format!("{}{}", IDN_ACE_PREFIX, self.to_string().to_lowercase())
+    }
+
+    /// Parses a label produced by [`Uuid::to_idn_label`]. The prefix is matched
+    /// case-insensitively, like DNS names.
+    pub fn from_idn_label(s: &str) -> Result<Self, UuidError> {
+        let prefix = s
+            .get(..IDN_ACE_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(IDN_ACE_PREFIX))
+            .ok_or_else(|| UuidError::InvalidEncoding("missing xn-- prefix".to_string()))?;
+        Uuid::try_from(&s[prefix.len()..])
+    }
+
+    /// The 120 bits as 11 words of a BIP-39 style `word_list`, 11 bits per word, most
+    /// significant first. The first word always comes from the first half of the list.
+    pub fn to_mnemonic_phrase(&self, word_list: &[&str; 2048]) -> Vec<String> {
+        let value = self.to_u128();
+        (0..MNEMONIC_WORDS)
+            .rev()
+            .map(|i| word_list[(value >> (11 * i)) as usize & 0x7ff].to_string())
+            .collect()
+    }
+
+    /// Parses a phrase produced by [`Uuid::to_mnemonic_phrase`] with the same list.
+    pub fn from_mnemonic_phrase(
+        words: &[&str],
+        word_list: &[&str; 2048],
+    ) -> Result<Self, UuidError> {
+        if words.len() != MNEMONIC_WORDS {
+            return Err(UuidError::InvalidEncoding(format!(
+                "expected {} words, got {}",
+                MNEMONIC_WORDS,
+                words.len()
+            )));
+        }
+        let mut value = 0u128;
+        for word in words {
+            let index = word_list
+                .iter()
+                .position(|candidate| candidate == word)
+                .ok_or_else(|| UuidError::InvalidEncoding(format!("unknown word {:?}", word)))?;
+            value = value << 11 | index as u128;
+        }
+        if value >> (BYTE_SIZE * 8) != 0 {
+            return Err(UuidError::InvalidEncoding(
+                "phrase exceeds 120 bits".to_string(),
+            ));
+        }
+        Ok(Uuid::from_u128(value))
+    }
+
+    /// Encodes the 15 bytes in `base`, prefixed with its multibase character.
+    #[cfg(feature = "multibase")]
+    pub fn to_multibase(&self, base: Multibase) -> String {
+        multibase::encode(base.base(), self.bytes.as_slice())
+    }
+
+    /// Decodes a multibase string in any of the [`Multibase`] bases. Base32 bodies are
+    /// decoded case insensitively, whichever of the two prefixes they carry.
+    #[cfg(feature = "multibase")]
+    pub fn from_multibase(s: &str) -> Result<Self, UuidError> {
+        let invalid = |reason: &str| UuidError::InvalidEncoding(reason.to_string());
+        let mut chars = s.chars();
+        let base = chars
+            .next()
+            .and_then(Multibase::from_prefix)
+            .ok_or_else(|| invalid("unsupported multibase prefix"))?;
+        let bytes = base
+            .base()
+            .decode(chars.as_str())
+            .map_err(|_| invalid("invalid multibase body"))?;
+
+        let bytes: [u8; BYTE_SIZE] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| UuidError::InvalidByteLength(bytes.len()))?;
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// Encodes an opaque GraphQL pagination cursor, the standard base64 of
+    /// `{"type":"<entity_type>","id":"<uuid>"}`.
+    #[cfg(feature = "graphql-cursor")]
+    pub fn to_graphql_cursor(&self, entity_type: &str) -> String {
+        let cursor = GraphqlCursor {
+            entity_type: entity_type.to_string(),
+            id: self.to_string(),
+        };
+        let json = serde_json::to_vec(&cursor).expect("a cursor always serializes");
+        base64::engine::general_purpose::STANDARD.encode(json)
+    }
+
+    /// Decodes a cursor produced by [`Uuid::to_graphql_cursor`] into its entity type and uuid.
+    #[cfg(feature = "graphql-cursor")]
+    pub fn from_graphql_cursor(cursor: &str) -> Result<(String, Self), UuidError> {
+        let invalid = |reason: &str| UuidError::InvalidEncoding(reason.to_string());
+        let json = base64::engine::general_purpose::STANDARD
+            .decode(cursor)
+            .map_err(|_| invalid("cursor is not base64"))?;
+        let cursor: GraphqlCursor =
+            serde_json::from_slice(&json).map_err(|e| invalid(&format!("cursor: {}", e)))?;
+        let id = Uuid::try_from(cursor.id.as_str())?;
+        Ok((cursor.entity_type, id))
+    }
+
+    /// A constant Rust expression for the uuid, for code generators, e.g.
+    /// `::crockford_uuid::Uuid::from_byte_array([0x26, 0x41, ...])`.
+    pub fn to_rust_literal(&self) -> String {
+        let bytes: Vec<String> = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect();
+        format!(
+            "::crockford_uuid::Uuid::from_byte_array([{}])",
+            bytes.join(", ")
+        )
+    }
+
+    /// A `pub const` item holding the uuid, e.g.
+    /// `pub const SYSTEM_USER: ::crockford_uuid::Uuid = ...;` with the expression of
+    /// [`Uuid::to_rust_literal`] and the id as a doc comment. `const_name` must be a
+    /// valid Rust identifier.
+    pub fn to_rust_const_definition(&self, const_name: &str) -> String {
+        format!(
+            "/// `{}`\npub const {}: ::crockford_uuid::Uuid = {};",
+            self,
+            const_name,
+            self.to_rust_literal()
+        )
+    }
+
+    /// The bytes as an Erlang binary literal, e.g. `<<38,65,225,...,119>>`.
+    pub fn to_erlang_binary(&self) -> String {
+        let bytes: Vec<String> = self.bytes.as_slice().iter().map(u8::to_string).collect();
+        format!("<<{}>>", bytes.join(","))
+    }
+
+    /// The bytes as an Elixir binary literal, which has the same syntax as
+    /// [`Uuid::to_erlang_binary`].
+    pub fn to_elixir_binary(&self) -> String {
+        self.to_erlang_binary()
+    }
+
+    /// Parses an Erlang or Elixir binary literal of 15 decimal bytes, e.g.
+    /// `<<38, 65, 225, ..., 119>>`. Spaces around the bytes are allowed.
+    pub fn from_erlang_binary(s: &str) -> Result<Self, UuidError> {
+        let inner = s
+            .trim()
+            .strip_prefix("<<")
+            .and_then(|s| s.strip_suffix(">>"))
+            .ok_or_else(|| UuidError::InvalidEncoding("not a << >> binary".to_string()))?;
+        let segments: Vec<&str> = inner.split(',').map(str::trim).collect();
+        if segments.len() != BYTE_SIZE {
+            return Err(UuidError::InvalidEncoding(format!(
+                "expected {} bytes, got {}",
+                BYTE_SIZE,
+                segments.len()
+            )));
+        }
+
+        let mut bytes = [0u8; BYTE_SIZE];
+        for (byte, segment) in bytes.iter_mut().zip(segments) {
+            if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(UuidError::InvalidEncoding(format!(
+                    "binary segment {:?} is not a decimal byte",
+                    segment
+                )));
+            }
+            *byte = segment.parse().map_err(|_| {
+                UuidError::InvalidEncoding(format!("binary segment {} exceeds 255", segment))
+            })?;
+        }
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// The bytes as a Python `bytes` literal with every byte escaped, e.g.
+    /// `b'\x26\x41...\x77'`.
+    pub fn to_python_bytes_literal(&self) -> String {
+        let escaped: String = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("\\x{:02x}", b))
+            .collect();
+        format!("b'{}'", escaped)
+    }
+
+    /// The uuid as a Python `int` literal, the 120 bit big-endian value in decimal.
+    pub fn to_python_int_literal(&self) -> String {
+        self.to_u128().to_string()
+    }
+
+    /// A Python `dict` literal of the encoding, the bytes and the integer value, e.g.
+    /// `{"uuid": "4S0Y2VZ7SF4VGHNZNYTZ9GVQ6", "bytes": b'\x26...', "int": 198...}`.
+    pub fn to_python_dict(&self) -> String {
+        format!(
+            "{{\"uuid\": \"{}\", \"bytes\": {}, \"int\": {}}}",
+            self,
+            self.to_python_bytes_literal(),
+            self.to_python_int_literal()
+        )
+    }
+
+    /// The bytes as a Go array literal, e.g. `[15]byte{0x26, 0x41, ..., 0x77}`.
+    pub fn to_go_byte_array(&self) -> String {
+        let bytes: Vec<String> = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect();
+        format!("[{}]byte{{{}}}", BYTE_SIZE, bytes.join(", "))
+    }
+
+    /// The encoding as a Go interpreted string literal. Crockford characters need no
+    /// escaping, so this is the id in double quotes.
+    pub fn to_go_string_literal(&self) -> String {
+        format!("\"{}\"", self)
+    }
+
+    /// A Go untyped string constant holding the encoding, e.g.
+    /// `const SystemUser = "4S0Y2VZ7SF4VGHNZNYTZ9GVQ6"`. `name` must be a valid Go
+    /// identifier.
+    pub fn to_go_const(&self, name: &str) -> String {
+        format!("const {} = {}", name, self.to_go_string_literal())
+    }
+
+    /// A C array definition holding the bytes, e.g.
+    /// `const uint8_t SYSTEM_USER[15] = {0x26, 0x41, ..., 0x77};`. `var_name` must be a
+    /// valid C identifier.
+    pub fn to_c_array(&self, var_name: &str) -> String {
+        let bytes: Vec<String> = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect();
+        format!(
+            "const uint8_t {}[{}] = {{{}}};",
+            var_name,
+            BYTE_SIZE,
+            bytes.join(", ")
+        )
+    }
+
+    /// The encoding as a C string literal, the id in double quotes.
+    pub fn to_c_string_literal(&self) -> String {
+        format!("\"{}\"", self)
+    }
+
+    /// The bytes as a C string literal with every byte hex escaped, e.g.
+    /// `"\x26\x41...\x77"`. The literal has an implicit trailing NUL, so use 15 as its
+    /// length rather than `sizeof`.
+    pub fn to_c_hex_string(&self) -> String {
+        let escaped: String = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("\\x{:02x}", b))
+            .collect();
+        format!("\"{}\"", escaped)
+    }
+
+    /// A Java `byte[]` declaration holding the bytes, e.g.
+    /// `byte[] systemUser = {(byte)0x26, (byte)0x41, (byte)-31, ..., (byte)0x77};`.
+    /// Java bytes are signed, so bytes above 127 are written as their negative value,
+    /// e.g. `(byte)-31` for `0xe1`. `var_name` must be a valid Java identifier.
+    pub fn to_java_byte_array(&self, var_name: &str) -> String {
+        let bytes: Vec<String> = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| match *b as i8 {
+                value if value < 0 => format!("(byte){}", value),
+                value => format!("(byte)0x{:02x}", value),
+            })
+            .collect();
+        format!("byte[] {} = {{{}}};", var_name, bytes.join(", "))
+    }
+
+    /// The encoding as a Java string literal, the id in double quotes.
+    pub fn to_java_string_literal(&self) -> String {
+        format!("\"{}\"", self)
+    }
+
+    /// A `java.util.UUID` expression for the uuid left-padded to 16 bytes, as in
+    /// [`Uuid::to_postgres_uuid_literal`], e.g.
+    /// `UUID.fromString("002641e1-6fe7-cbc9-b846-bfafb5f4c377")`.
+    pub fn to_java_uuid_string(&self) -> String {
+        let literal = self.to_postgres_uuid_literal();
+        format!("UUID.fromString(\"{}\")", literal.trim_matches('\''))
+    }
+
+    /// The uuid as a CBOR data item: tag 37, the IANA registered tag for binary uuids,
+    /// wrapping a 15 byte string, i.e. `0xd8 0x25 0x4f` followed by the bytes.
+    ///
+    /// RFC 9562 and the tag registry define tag 37 over 16 byte RFC 4122 uuids. Using it
+    /// for 15 byte ids is an extension of this crate: generic CBOR decoders that validate
+    /// the length will reject these items.
+    #[cfg(feature = "cbor-tag")]
+    pub fn to_cbor_tag(&self) -> Vec<u8> {
+        let mut cbor = Vec::with_capacity(CBOR_UUID_HEADER.len() + BYTE_SIZE);
+        cbor.extend_from_slice(&CBOR_UUID_HEADER);
+        cbor.extend_from_slice(self.bytes.as_slice());
+        cbor
+    }
+
+    /// Reads the item written by [`Uuid::to_cbor_tag`]. Anything but tag 37 over a 15
+    /// byte string, including trailing data, fails with [`UuidError::InvalidEncoding`].
+    #[cfg(feature = "cbor-tag")]
+    pub fn from_cbor_tag(data: &[u8]) -> Result<Self, UuidError> {
+        let bytes = data.strip_prefix(&CBOR_UUID_HEADER).ok_or_else(|| {
+            UuidError::InvalidEncoding("expected tag 37 and a 15 byte string".to_string())
+        })?;
+        let bytes: [u8; BYTE_SIZE] = bytes.try_into().map_err(|_| {
+            UuidError::InvalidEncoding(format!(
+                "{} bytes after the header, expected {}",
+                bytes.len(),
+                BYTE_SIZE
+            ))
+        })?;
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// A JSON object of the encoding, the bytes in hex and the checksum, without
+    /// whitespace, e.g. `{"v":"4S0Y2VZ7SF4VGHNZNYTZ9GVQ6","b":"2641e16fe7cbc9b846bfafb5f4c377","cs":6}`,
+    /// for debugging.
+    pub fn to_compact_json(&self) -> String {
+        let (v, b, cs) = self.json_fields();
+        format!("{{\"v\":\"{}\",\"b\":\"{}\",\"cs\":{}}}", v, b, cs)
+    }
+
+    /// [`Uuid::to_compact_json`] over several lines, indented by 2 spaces.
+    pub fn to_pretty_json(&self) -> String {
+        let (v, b, cs) = self.json_fields();
+        format!(
+            "{{\n  \"v\": \"{}\",\n  \"b\": \"{}\",\n  \"cs\": {}\n}}",
+            v, b, cs
+        )
+    }
+
+    fn json_fields(&self) -> (UuidString, String, u8) {
+        let hex = self
+            .bytes
+            .as_slice()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        (self.to_string_stack(), hex, self.checksum)
+    }
+
+    /// Parses the `"v"` field of [`Uuid::to_compact_json`] or [`Uuid::to_pretty_json`],
+    /// ignoring the others.
+    pub fn from_compact_json(s: &str) -> Result<Self, UuidError> {
+        let invalid = |reason: &str| UuidError::InvalidEncoding(reason.to_string());
+        let fields = parse_json_flat_object(s).ok_or_else(|| invalid("not a flat json object"))?;
+        let (_, value) = fields
+            .iter()
+            .find(|(key, _)| key == "v")
+            .ok_or_else(|| invalid("no \"v\" field"))?;
+        Uuid::try_from(value.as_str())
+    }
+
+    #[cfg(feature = "sqids")]
+    fn sqids(alphabet: Option<&str>) -> Result<sqids::Sqids, UuidError> {
+        let builder = sqids::Sqids::builder();
+        let builder = match alphabet {
+            Some(alphabet) => builder.alphabet(alphabet.chars().collect()),
+            None => builder,
+        };
+        builder
+            .build()
+            .map_err(|e| UuidError::InvalidEncoding(e.to_string()))
+    }
+
+    /// Encodes the uuid with the [Sqids](https://sqids.org) algorithm.
+    ///
+    /// The 120 bits are split into two 60 bit chunks, most significant first.
+    /// `alphabet` defaults to the sqids default alphabet, which gives at most 24
+    /// characters, shorter than the canonical encoding. Fails with
+    /// [`UuidError::InvalidEncoding`] if `alphabet` is not a valid sqids alphabet.
+    #[cfg(feature = "sqids")]
+    pub fn to_sqids(&self, alphabet: Option<&str>) -> Result<String, UuidError> {
+        let mut value = [0u8; 16];
+        value[1..].copy_from_slice(self.bytes.as_slice());
+        let value = u128::from_be_bytes(value);
+        let mask = (1u128 << SQIDS_CHUNK_BITS) - 1;
+        let chunks: Vec<u64> = (0..SQIDS_CHUNKS)
+            .rev()
+            .map(|i| ((value >> (i * SQIDS_CHUNK_BITS)) & mask) as u64)
+            .collect();
+
+        Uuid::sqids(alphabet)?
+            .encode(&chunks)
+            .map_err(|e| UuidError::InvalidEncoding(e.to_string()))
+    }
+
+    /// Decodes a uuid produced by [`Uuid::to_sqids`] with the same alphabet.
+    #[cfg(feature = "sqids")]
+    pub fn from_sqids(s: &str, alphabet: Option<&str>) -> Result<Self, UuidError> {
+        let sqids = Uuid::sqids(alphabet)?;
+        let chunks = sqids.decode(s);
+        if chunks.len() != SQIDS_CHUNKS as usize
+            || chunks.iter().any(|c| *c >> SQIDS_CHUNK_BITS != 0)
+        {
+            return Err(UuidError::InvalidEncoding("not a uuid sqid".to_string()));
+        }
+        // several strings can decode to the same numbers, only accept the canonical one
+        if sqids.encode(&chunks).ok().as_deref() != Some(s) {
+            return Err(UuidError::InvalidEncoding("non canonical sqid".to_string()));
+        }
+
+        let value = chunks
+            .iter()
+            .fold(0u128, |acc, c| (acc << SQIDS_CHUNK_BITS) | *c as u128);
+        let bytes = Bytes::try_from(value.to_be_bytes()[1..].to_vec())?;
+        let checksum = bytes.derive_crockford_checksum();
+        Ok(Self { bytes, checksum })
+    }
+}
+
+/// Describes the checksum scheme, as returned by [`Uuid::checksum_algorithm`]: the
+/// checksum character is `alphabet[value % modulo]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumInfo {
+    pub modulo: usize,
+    pub alphabet: &'static str,
+    pub alphabet_len: usize,
+}
+
+/// The 25 character encoding of a uuid held inline, returned by [`Uuid::to_string_stack`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UuidString([u8; ENCODED_LEN]);
+
+impl UuidString {
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("crockford characters are ascii")
+    }
+}
+
+impl std::ops::Deref for UuidString {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for UuidString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::borrow::Borrow<str> for UuidString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// must agree with `str`'s hash for the `Borrow<str>` lookups
+impl std::hash::Hash for UuidString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialEq<str> for UuidString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for UuidString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::fmt::Display for UuidString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Debug for UuidString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for UuidString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for Uuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.encode_crockford(&mut [0; ENCODED_LEN]))
+    }
+}
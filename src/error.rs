@@ -0,0 +1,88 @@
+use crate::uuid::{Uuid, BYTE_SIZE};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UuidError {
+    /// The input did not have the expected number of characters.
+    InvalidLength(usize),
+    /// The input did not have the expected number of bytes.
+    InvalidByteLength(usize),
+    /// The byte at `position` is not part of the crockford alphabet.
+    InvalidCharacter { position: usize, byte: u8 },
+    /// The checksum character does not match the identifier.
+    ChecksumMismatch,
+    /// The input is not a valid uuid in an alternative encoding.
+    InvalidEncoding(String),
+    /// The system RNG failed on every attempt.
+    RngExhausted,
+    /// The secret shares are inconsistent or too few to reconstruct a uuid.
+    InvalidShares(String),
+    /// The ciphertext was not produced by this key or has been tampered with.
+    DecryptionFailed,
+    /// A builder or generator was configured with incompatible options.
+    InvalidConfiguration(String),
+    /// Every attempt of a [`FilteredGenerator`](crate::FilteredGenerator) produced a blocklisted id.
+    FilterExhausted(u32),
+    /// Every attempt of a [`UniqueGenerator`](crate::UniqueGenerator) produced an id it had
+    /// already issued.
+    UniquenessExhausted(u32),
+    /// A timestamp doesn't fit an id: it is before the epoch it is counted from, or
+    /// beyond the 48 bits of a sortable id.
+    InvalidTimestamp,
+    /// The clock read `now_ms`, before the timestamp of the last id, `last_ms`, under
+    /// [`ClockRollbackPolicy::Error`](crate::ClockRollbackPolicy::Error).
+    ClockRolledBack { last_ms: u64, now_ms: u64 },
+    /// A shard of a [`ConcurrentUuidInterner`](crate::ConcurrentUuidInterner) has issued
+    /// every handle it can.
+    InternerFull,
+}
+
+impl std::fmt::Display for UuidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UuidError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "invalid string length {}, expected {}",
+                    len,
+                    Uuid::encoded_length_with_checksum()
+                )
+            }
+            UuidError::InvalidByteLength(len) => {
+                write!(f, "invalid byte length {}, expected {}", len, BYTE_SIZE)
+            }
+            UuidError::InvalidCharacter { position, byte } => {
+                write!(
+                    f,
+                    "invalid character 0x{:02x} at position {}",
+                    byte, position
+                )
+            }
+            UuidError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            UuidError::InvalidEncoding(reason) => write!(f, "invalid encoding: {}", reason),
+            UuidError::RngExhausted => write!(f, "failed to generate random bytes"),
+            UuidError::InvalidShares(reason) => write!(f, "invalid shares: {}", reason),
+            UuidError::DecryptionFailed => write!(f, "decryption failed"),
+            UuidError::InvalidConfiguration(reason) => {
+                write!(f, "invalid configuration: {}", reason)
+            }
+            UuidError::FilterExhausted(attempts) => {
+                write!(f, "all {} generated ids were blocklisted", attempts)
+            }
+            UuidError::UniquenessExhausted(attempts) => {
+                write!(f, "all {} generated ids were duplicates", attempts)
+            }
+            UuidError::InvalidTimestamp => write!(f, "timestamp out of range"),
+            UuidError::ClockRolledBack { last_ms, now_ms } => write!(
+                f,
+                "clock moved back {} ms behind the last id",
+                last_ms.saturating_sub(*now_ms)
+            ),
+            UuidError::InternerFull => write!(f, "interner is full"),
+        }
+    }
+}
+
+impl std::error::Error for UuidError {}
+
+/// Shorthand for [`UuidError`], the error type of every fallible operation in the crate.
+pub type Error = UuidError;
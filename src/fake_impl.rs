@@ -0,0 +1,9 @@
+use crate::uuid::{Uuid, BYTE_SIZE};
+use fake::{Dummy, Faker, RngExt};
+
+/// 15 random bytes drawn from the faker's rng, so seeded fakers give the same ids.
+impl Dummy<Faker> for Uuid {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        Uuid::from_array(rng.random::<[u8; BYTE_SIZE]>())
+    }
+}
@@ -0,0 +1,1656 @@
+use crate::encode::{CROCKFORD_CHARS, CROCKFORD_CHECKSUM_CHARS, ENCODED_LEN};
+use crate::error::UuidError;
+use crate::uuid::{Bytes, Uuid, BYTE_SIZE, CHECKSUM_MODULO};
+use ring::aead;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_RNG_RETRIES: u32 = 3;
+const ENTROPY_POOL_SIZE: usize = 4096;
+const SORTABLE_ENTROPY_SIZE: usize = 9;
+const SORTABLE_TIMESTAMP_BITS: u32 = 48;
+const DATED_PREFIX_LEN: usize = 6;
+const DATED_FIRST_YEAR: i64 = 2000;
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+const COMBINE_TAG: &[u8] = b"crockford-uuid/combine/v1";
+const COMBINE_ORDERED_TAG: &[u8] = b"crockford-uuid/combine-ordered/v1";
+// must match `SEED_TAG` in crockford-uuid-macros
+const SEED_TAG: &[u8] = b"crockford-uuid/seed/v1";
+const UNIQUE_MAX_ATTEMPTS: u32 = 16;
+const NODE_ID_SIZE: usize = 2;
+const WRITE_BATCH_SIZE: usize = 1024;
+const TRACEABLE_HOST_SIZE: usize = 3;
+const TRACEABLE_RANDOM_SIZE: usize = 3;
+#[cfg(feature = "async")]
+const FLOOD_WARN_RATE: u32 = 1_000_000;
+#[cfg(feature = "crypto")]
+const DERIVE_SALT: &[u8] = b"crockford-uuid/derive/v1";
+/// How far in the future a sortable uuid's timestamp may be before it is considered invalid.
+pub const DEFAULT_CLOCK_SKEW_TOLERANCE: Duration = Duration::from_secs(1);
+
+pub(crate) fn rng() -> &'static SystemRandom {
+    static RANDOM: OnceLock<SystemRandom> = OnceLock::new();
+    RANDOM.get_or_init(SystemRandom::new)
+}
+
+// Entropy drawn from the system RNG in bulk and handed out per thread, so that the
+// generation hot path takes no lock and rarely makes a syscall. Each byte is used once.
+struct EntropyPool {
+    buffer: [u8; ENTROPY_POOL_SIZE],
+    position: usize,
+    // a forked child must not replay its parent's buffered entropy
+    pid: u32,
+}
+
+thread_local! {
+    static ENTROPY: RefCell<EntropyPool> = const {
+        RefCell::new(EntropyPool {
+            buffer: [0; ENTROPY_POOL_SIZE],
+            position: ENTROPY_POOL_SIZE,
+            pid: 0,
+        })
+    };
+}
+
+fn fill_from_pool(dest: &mut [u8]) -> Result<(), ring::error::Unspecified> {
+    if dest.len() > ENTROPY_POOL_SIZE {
+        return rng().fill(dest);
+    }
+    // the pool is gone while thread locals are being destroyed, e.g. when an id is
+    // generated from another thread local's destructor
+    ENTROPY
+        .try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            let pid = std::process::id();
+            if pool.pid != pid || ENTROPY_POOL_SIZE - pool.position < dest.len() {
+                rng().fill(&mut pool.buffer)?;
+                pool.position = 0;
+                pool.pid = pid;
+            }
+            let start = pool.position;
+            dest.copy_from_slice(&pool.buffer[start..start + dest.len()]);
+            // never hand out the same bytes twice, even if the caller discards them
+            pool.buffer[start..start + dest.len()].fill(0);
+            pool.position += dest.len();
+            Ok(())
+        })
+        .unwrap_or_else(|_| rng().fill(dest))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    fill_from_pool(&mut bytes).expect("failed to generate random bytes");
+    bytes
+}
+
+fn unix_ms(time: SystemTime) -> Result<u64, UuidError> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| UuidError::InvalidTimestamp)?
+        .as_millis();
+    u64::try_from(millis).map_err(|_| UuidError::InvalidTimestamp)
+}
+
+// days since the unix epoch to a (year, month, day) civil date, after Howard Hinnant's
+// `civil_from_days`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+fn now_ms() -> u64 {
+    unix_ms(SystemTime::now()).expect("system time is before the unix epoch")
+}
+
+// big-endian increment, wrapping on overflow
+fn increment(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut().rev() {
+        let (value, overflow) = byte.overflowing_add(1);
+        *byte = value;
+        if !overflow {
+            break;
+        }
+    }
+}
+
+// HKDF-SHA256 (RFC 5869) truncated to a uuid's worth of output key material
+#[cfg(feature = "crypto")]
+pub(crate) fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8]) -> [u8; BYTE_SIZE] {
+    use ring::hkdf::{KeyType, Salt, HKDF_SHA256};
+
+    struct Len;
+    impl KeyType for Len {
+        fn len(&self) -> usize {
+            BYTE_SIZE
+        }
+    }
+
+    let mut okm = [0u8; BYTE_SIZE];
+    Salt::new(HKDF_SHA256, salt)
+        .extract(ikm)
+        .expand(&[info], Len)
+        .and_then(|expanded| expanded.fill(&mut okm))
+        .expect("15 bytes is a valid HKDF-SHA256 output length");
+    okm
+}
+
+/// A source of unix timestamps in milliseconds for [`Uuid::generate_with_clock`].
+pub trait MonotonicClock: Send + Sync {
+    fn now_ms(&self) -> u64;
+}
+
+/// The system time, as used by [`Uuid::new_sortable`]. It can go backwards when the
+/// system clock is adjusted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl MonotonicClock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        now_ms()
+    }
+}
+
+static DEFAULT_GENERATOR: OnceLock<Mutex<Generator>> = OnceLock::new();
+
+thread_local! {
+    static SCOPED_GENERATORS: RefCell<Vec<Generator>> = const { RefCell::new(Vec::new()) };
+    static IN_DEFAULT_GENERATOR: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Makes [`Uuid::new`] draw from `generator` for the rest of the process, on every
+/// thread, unless a [`with_generator`] scope overrides it. Can be called once, later
+/// calls fail with [`UuidError::InvalidConfiguration`].
+///
+/// Precedence, highest first: the innermost [`with_generator`] scope on the calling
+/// thread, the default generator, the system RNG.
+pub fn set_default_generator(generator: Generator) -> Result<(), UuidError> {
+    DEFAULT_GENERATOR
+        .set(Mutex::new(generator))
+        .map_err(|_| UuidError::InvalidConfiguration("default generator already set".to_string()))
+}
+
+/// Runs `f` with [`Uuid::new`] drawing from `generator` on the calling thread only, so
+/// that parallel tests can each use their own generator. Scopes nest, and the previous
+/// generator is restored when `f` returns or panics.
+pub fn with_generator<F, R>(generator: Generator, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore;
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_GENERATORS.with(|scoped| scoped.borrow_mut().pop());
+        }
+    }
+
+    SCOPED_GENERATORS.with(|scoped| scoped.borrow_mut().push(generator));
+    let _restore = Restore;
+    f()
+}
+
+/// `Uuid::generate_test_uuid(0)`. Not for production use.
+pub const TEST_UUID_0: Uuid = Uuid::generate_test_uuid(0);
+/// `Uuid::generate_test_uuid(1)`. Not for production use.
+pub const TEST_UUID_1: Uuid = Uuid::generate_test_uuid(1);
+/// `Uuid::generate_test_uuid(u32::MAX)`. Not for production use.
+pub const TEST_UUID_MAX: Uuid = Uuid::generate_test_uuid(u32::MAX);
+
+impl Uuid {
+    /// Generates a random uuid, or one from the generator installed with
+    /// [`with_generator`] or [`set_default_generator`].
+    ///
+    /// A generator's rng, clock, filter or hooks may call `Uuid::new` themselves: while
+    /// a generator runs, such calls draw from the next source in the precedence order.
+    ///
+    /// # Panics
+    ///
+    /// If the generator fails.
+    pub fn new() -> Self {
+        // puts the innermost scoped generator back once it has run, even on a panic
+        struct PutBack(Option<Generator>);
+        impl Drop for PutBack {
+            fn drop(&mut self) {
+                if let Some(generator) = self.0.take() {
+                    SCOPED_GENERATORS.with(|scoped| scoped.borrow_mut().push(generator));
+                }
+            }
+        }
+        struct LeaveDefault;
+        impl Drop for LeaveDefault {
+            fn drop(&mut self) {
+                IN_DEFAULT_GENERATOR.set(false);
+            }
+        }
+
+        let generated = match SCOPED_GENERATORS.with(|scoped| scoped.borrow_mut().pop()) {
+            Some(generator) => {
+                let mut put_back = PutBack(Some(generator));
+                put_back.0.as_mut().expect("taken above").generate()
+            }
+            None => match DEFAULT_GENERATOR.get() {
+                Some(generator) if !IN_DEFAULT_GENERATOR.get() => {
+                    IN_DEFAULT_GENERATOR.set(true);
+                    let _leave = LeaveDefault;
+                    // a panic in another caller's rng leaves the generator usable
+                    generator
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .generate()
+                }
+                _ => Uuid::generate_secure(),
+            },
+        };
+        generated.expect("failed to generate random bytes")
+    }
+
+    /// Deterministically derives a uuid from input key material, e.g. a tenant secret,
+    /// and an `info` string such as an object index.
+    ///
+    /// HKDF-SHA256 (RFC 5869) extracting with the fixed salt `crockford-uuid/derive/v1`
+    /// and expanding `info` to 15 bytes. This construction will never change: the same
+    /// inputs give the same uuid in every release.
+    #[cfg(feature = "crypto")]
+    pub fn derive(ikm: &[u8], info: &[u8]) -> Self {
+        Uuid::from_array(hkdf_sha256(DERIVE_SALT, ikm, info))
+    }
+
+    /// `n` reproducible uuids for fixtures and mock data, cut from the ChaCha20 keystream
+    /// keyed with `seed`. The same seed always gives the same ids in the same order, and
+    /// a longer batch starts with the ids of a shorter one. Not for production ids.
+    #[cfg(feature = "deterministic")]
+    pub fn generate_deterministic_batch(seed: &[u8; 32], n: usize) -> Vec<Uuid> {
+        use rand_chacha::rand_core::{Rng, SeedableRng};
+
+        let mut stream = vec![0u8; n * BYTE_SIZE];
+        rand_chacha::ChaCha20Rng::from_seed(*seed).fill_bytes(&mut stream);
+        stream
+            .chunks_exact(BYTE_SIZE)
+            .map(|chunk| {
+                let mut bytes = [0u8; BYTE_SIZE];
+                bytes.copy_from_slice(chunk);
+                Uuid::from_array(bytes)
+            })
+            .collect()
+    }
+
+    /// A content addressed uuid: the first 15 bytes of the SHA-256 digest of `data`.
+    pub fn from_data(data: &[u8]) -> Self {
+        Uuid::from_sha256(&[data])
+    }
+
+    /// Like [`Uuid::from_data`], but streams the content from `reader` in chunks
+    /// instead of holding it in memory.
+    pub fn from_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        let mut buffer = [0u8; READ_CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => context.update(&buffer[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        let digest = context.finish();
+        Ok(Uuid::from_array(
+            digest.as_ref()[..BYTE_SIZE].try_into().unwrap(),
+        ))
+    }
+
+    fn from_sha256(parts: &[&[u8]]) -> Self {
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        parts.iter().for_each(|part| context.update(part));
+        let digest = context.finish();
+        Uuid::from_array(digest.as_ref()[..BYTE_SIZE].try_into().unwrap())
+    }
+
+    /// A deterministic id named by `seed`, e.g. a well-known id such as a default
+    /// workspace, also available at build time as `gen_uuid!("seed")`.
+    ///
+    /// Stable construction: the first 15 bytes of SHA-256 over the ascii tag
+    /// `crockford-uuid/seed/v1` followed by the UTF-8 bytes of `seed`.
+    pub fn from_seed(seed: &str) -> Self {
+        Uuid::from_sha256(&[SEED_TAG, seed.as_bytes()])
+    }
+
+    /// A deterministic id for the undirected pair `{a, b}`, e.g. a graph edge.
+    ///
+    /// Stable construction: the first 15 bytes of SHA-256 over the ascii tag
+    /// `crockford-uuid/combine/v1` followed by the raw bytes of the smaller and then
+    /// the larger uuid, so `combine(a, b) == combine(b, a)`.
+    pub fn combine(a: &Uuid, b: &Uuid) -> Self {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        Uuid::from_sha256(&[COMBINE_TAG, low.bytes.as_slice(), high.bytes.as_slice()])
+    }
+
+    /// A deterministic id for the directed pair `a -> b`.
+    ///
+    /// Stable construction: the first 15 bytes of SHA-256 over the ascii tag
+    /// `crockford-uuid/combine-ordered/v1` followed by the raw bytes of `a` and `b`.
+    pub fn combine_ordered(a: &Uuid, b: &Uuid) -> Self {
+        Uuid::from_sha256(&[COMBINE_ORDERED_TAG, a.bytes.as_slice(), b.bytes.as_slice()])
+    }
+
+    /// A predictable uuid holding `index` big-endian in its last 4 bytes, for test
+    /// fixtures. Not for production use: these ids are not random.
+    pub const fn generate_test_uuid(index: u32) -> Self {
+        let index = index.to_be_bytes();
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes[BYTE_SIZE - 4] = index[0];
+        bytes[BYTE_SIZE - 3] = index[1];
+        bytes[BYTE_SIZE - 2] = index[2];
+        bytes[BYTE_SIZE - 1] = index[3];
+        Uuid::from_array(bytes)
+    }
+
+    /// Generates a uuid, retrying up to 3 times if the system RNG fails.
+    pub fn generate_secure() -> Result<Self, UuidError> {
+        Uuid::generate_secure_with_retries(DEFAULT_RNG_RETRIES)
+    }
+
+    /// Generates a uuid, attempting to fill it from the system RNG up to
+    /// `max_retries` times before giving up with [`UuidError::RngExhausted`].
+    pub fn generate_secure_with_retries(max_retries: u32) -> Result<Self, UuidError> {
+        Uuid::generate_with_fill(max_retries, fill_from_pool)
+    }
+
+    pub(crate) fn generate_with_fill<F>(max_retries: u32, mut fill: F) -> Result<Self, UuidError>
+    where
+        F: FnMut(&mut [u8]) -> Result<(), ring::error::Unspecified>,
+    {
+        for _ in 0..max_retries {
+            if let Ok(bytes) = Bytes::new_with(&mut fill) {
+                let checksum = bytes.derive_crockford_checksum();
+                return Ok(Self { bytes, checksum });
+            }
+        }
+        Err(UuidError::RngExhausted)
+    }
+
+    /// Generates a random RFC 4122 version 4 uuid and keeps its first 15 bytes, so that
+    /// the version nibble (high nibble of byte 6) is `4` and the variant bits (top two
+    /// bits of byte 8) are `10`. Only 7 bits less random than [`Uuid::new`].
+    pub fn generate_v4_compat() -> Self {
+        let mut bytes: [u8; 16] = random_bytes();
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Uuid::from_array(bytes[..BYTE_SIZE].try_into().unwrap())
+    }
+
+    /// Whether the version and variant bits match [`Uuid::generate_v4_compat`]. About
+    /// 1 in 64 random uuids match by chance.
+    pub fn is_v4_compat(&self) -> bool {
+        let bytes = self.bytes.as_slice();
+        bytes[6] >> 4 == 4 && bytes[8] >> 6 == 0b10
+    }
+
+    /// Generates `n` random uuids in strictly ascending byte order.
+    pub fn new_batch_sorted(n: usize) -> Vec<Self> {
+        let mut batch: Vec<Self> = Vec::with_capacity(n);
+        while batch.len() < n {
+            batch.extend((batch.len()..n).map(|_| Uuid::new()));
+            batch.sort();
+            // a duplicate is astronomically unlikely, but the batch must be strictly increasing
+            batch.dedup();
+        }
+        batch
+    }
+
+    /// Generates a time sortable uuid: the first 6 bytes hold the big-endian unix
+    /// timestamp in milliseconds, the remaining 9 bytes are random.
+    pub fn new_sortable() -> Self {
+        Uuid::sortable(now_ms(), random_bytes())
+    }
+
+    /// Generates a time sortable uuid like [`Uuid::new_sortable`], with the timestamp
+    /// read from `clock`, e.g. a fixed clock in tests. Only the low 48 bits of the
+    /// timestamp are kept.
+    pub fn generate_with_clock(clock: &dyn MonotonicClock) -> Self {
+        Uuid::sortable(clock.now_ms(), random_bytes())
+    }
+
+    /// Generates a uuid whose first 8 bytes are the big-endian value fetched from
+    /// `counter`, incrementing it, followed by 7 random bytes. Ids drawn from the same
+    /// counter are unique and ordered by issue, across threads and without locking.
+    pub fn generate_with_counter(counter: &AtomicU64) -> Self {
+        let value = counter.fetch_add(1, Ordering::Relaxed);
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes[..8].copy_from_slice(&value.to_be_bytes());
+        bytes[8..].copy_from_slice(&random_bytes::<{ BYTE_SIZE - 8 }>());
+        Uuid::from_array(bytes)
+    }
+
+    /// Generates random uuids until the 24 character body, checksum excluded, ends with
+    /// `suffix`, compared case-insensitively. Each character of the suffix makes a
+    /// match 32 times less likely. Returns `None` after `max_attempts`, or at once if
+    /// the suffix is too long or not in the crockford alphabet.
+    pub fn generate_vanity_suffix(suffix: &str, max_attempts: u64) -> Option<Self> {
+        let suffix = suffix.to_ascii_uppercase();
+        let body_len = Uuid::encoded_length();
+        if suffix.len() > body_len || !suffix.bytes().all(|b| CROCKFORD_CHARS.contains(&b)) {
+            return None;
+        }
+        (0..max_attempts)
+            .map(|_| Uuid::new())
+            .find(|uuid| uuid.encode()[..body_len].ends_with(suffix.as_bytes()))
+    }
+
+    /// An endless stream of [`Uuid::new`] ids, one every `1 / rate_per_second` seconds,
+    /// for load testing. The first id arrives after one period. Ticks missed by a slow
+    /// consumer are yielded in a burst to keep the average rate. A rate of 0 gives an
+    /// empty stream; rates above a million per second log a warning, as timers rarely
+    /// keep up, and rates above a billion per second are capped at one id per
+    /// nanosecond, the timer's resolution.
+    ///
+    /// # Panics
+    ///
+    /// If called outside a tokio runtime.
+    #[cfg(feature = "async")]
+    pub fn generate_flood(rate_per_second: u32) -> impl tokio_stream::Stream<Item = Uuid> {
+        use tokio_stream::StreamExt;
+
+        if rate_per_second > FLOOD_WARN_RATE {
+            log::warn!(
+                "generating {} ids per second, timers are unlikely to keep up",
+                rate_per_second
+            );
+        }
+        let period = Duration::from_secs_f64(1.0 / rate_per_second.max(1) as f64)
+            .max(Duration::from_nanos(1));
+        let interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        let limit = if rate_per_second == 0 { 0 } else { usize::MAX };
+        tokio_stream::wrappers::IntervalStream::new(interval)
+            .take(limit)
+            .map(|_| Uuid::new())
+    }
+
+    /// The counter value of an id from [`Uuid::generate_with_counter`].
+    pub fn counter_value(&self) -> u64 {
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&self.bytes.as_slice()[..8]);
+        u64::from_be_bytes(value)
+    }
+
+    /// Generates `n` time sortable uuids in strictly ascending byte order.
+    ///
+    /// All ids share a single timestamp and the entropy tail of each id is the previous
+    /// one incremented by one, starting from a random value whose top bit is cleared so
+    /// that the tail can't overflow.
+    pub fn new_sortable_batch_sorted(n: usize) -> Vec<Self> {
+        let millis = now_ms();
+        let mut entropy: [u8; SORTABLE_ENTROPY_SIZE] = random_bytes();
+        entropy[0] &= 0x7f;
+
+        (0..n)
+            .map(|_| {
+                let uuid = Uuid::sortable(millis, entropy);
+                increment(&mut entropy);
+                uuid
+            })
+            .collect()
+    }
+
+    /// A time sortable uuid, as made by [`Uuid::new_sortable`], from a unix timestamp in
+    /// milliseconds and the 9 byte entropy tail, e.g. to backfill historical events.
+    /// Fails with [`UuidError::InvalidTimestamp`] if `millis` doesn't fit in 48 bits.
+    ///
+    /// Ids built this way sort with live ones by timestamp, then by entropy.
+    pub fn sortable_from_parts(
+        millis: u64,
+        entropy: [u8; SORTABLE_ENTROPY_SIZE],
+    ) -> Result<Self, UuidError> {
+        if millis >> SORTABLE_TIMESTAMP_BITS != 0 {
+            return Err(UuidError::InvalidTimestamp);
+        }
+        Ok(Uuid::sortable(millis, entropy))
+    }
+
+    /// [`Uuid::sortable_from_parts`] with a random entropy tail.
+    pub fn sortable_at(millis: u64) -> Result<Self, UuidError> {
+        Uuid::sortable_from_parts(millis, random_bytes())
+    }
+
+    /// Generates a random uuid whose leading `bit_len` bits are the leading `bit_len`
+    /// bits of `prefix_bits`, read most significant bit first, e.g. to keep a tenant's
+    /// ids inside the range it was assigned. With a multiple of 5 bits the ids fall in
+    /// [`Uuid::range_for_prefix`] of the matching characters.
+    ///
+    /// Fails with [`UuidError::InvalidConfiguration`] if `bit_len` is over 120 or
+    /// `prefix_bits` is shorter than `bit_len` bits.
+    pub fn random_with_prefix(prefix_bits: &[u8], bit_len: u8) -> Result<Self, UuidError> {
+        let bit_len = bit_len as usize;
+        if bit_len > BYTE_SIZE * 8 {
+            return Err(UuidError::InvalidConfiguration(format!(
+                "a prefix of {} bits does not fit in {} bits",
+                bit_len,
+                BYTE_SIZE * 8
+            )));
+        }
+        if prefix_bits.len() * 8 < bit_len {
+            return Err(UuidError::InvalidConfiguration(format!(
+                "{} prefix bytes hold fewer than {} bits",
+                prefix_bits.len(),
+                bit_len
+            )));
+        }
+
+        let mut bytes: [u8; BYTE_SIZE] = random_bytes();
+        let whole = bit_len / 8;
+        bytes[..whole].copy_from_slice(&prefix_bits[..whole]);
+        let partial = bit_len % 8;
+        if partial > 0 {
+            let mask = 0xffu8 << (8 - partial);
+            bytes[whole] = (prefix_bits[whole] & mask) | (bytes[whole] & !mask);
+        }
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// The 9 byte entropy tail of a sortable uuid, after its timestamp.
+    pub fn sortable_entropy(&self) -> [u8; SORTABLE_ENTROPY_SIZE] {
+        let mut entropy = [0u8; SORTABLE_ENTROPY_SIZE];
+        entropy.copy_from_slice(&self.bytes.as_slice()[BYTE_SIZE - SORTABLE_ENTROPY_SIZE..]);
+        entropy
+    }
+
+    pub(crate) fn sortable(millis: u64, entropy: [u8; SORTABLE_ENTROPY_SIZE]) -> Self {
+        let mut bytes = Vec::with_capacity(BYTE_SIZE);
+        bytes.extend_from_slice(&millis.to_be_bytes()[2..]);
+        bytes.extend_from_slice(&entropy);
+        let bytes = Bytes::try_from(bytes).expect("sortable bytes are always valid");
+        let checksum = bytes.derive_crockford_checksum();
+        Self { bytes, checksum }
+    }
+
+    /// Reads the unix timestamp in milliseconds from the first 6 bytes of a sortable uuid.
+    pub fn timestamp_ms(&self) -> u64 {
+        let mut millis = [0u8; 8];
+        millis[2..].copy_from_slice(&self.bytes.as_slice()[..6]);
+        u64::from_be_bytes(millis)
+    }
+
+    /// Generates a time sortable uuid like [`Uuid::new_sortable`], but with the
+    /// milliseconds counted from a custom `epoch`, e.g. a founding date, rather than the
+    /// unix epoch. Fails with [`UuidError::InvalidTimestamp`] if `epoch` is in the future.
+    pub fn generate_with_epoch(epoch: SystemTime) -> Result<Self, UuidError> {
+        let millis = SystemTime::now()
+            .duration_since(epoch)
+            .map_err(|_| UuidError::InvalidTimestamp)?
+            .as_millis();
+        Uuid::sortable_at(u64::try_from(millis).map_err(|_| UuidError::InvalidTimestamp)?)
+    }
+
+    /// The milliseconds since `epoch` stored by [`Uuid::generate_with_epoch`]. The id
+    /// doesn't record its epoch, so this is the raw value of [`Uuid::timestamp_ms`];
+    /// `epoch` only documents which one the caller expects.
+    pub fn timestamp_since_epoch_ms(&self, _epoch: SystemTime) -> u64 {
+        self.timestamp_ms()
+    }
+
+    /// Generates a uuid whose encoding starts with the current UTC date as `YYMMDD`.
+    ///
+    /// The first 30 bits hold six 5 bit values, each a decimal digit of the date, so
+    /// the first six characters of the string literally read as the date: an id created
+    /// on 2026-10-16 starts with `261016`. The remaining 90 bits are random. Dated ids
+    /// are for humans eyeballing labels and make no ordering guarantees.
+    ///
+    /// The two digit year only covers 2000 to 2099, so this fails with
+    /// [`UuidError::InvalidTimestamp`] for any other date.
+    pub fn new_dated() -> Result<Self, UuidError> {
+        Uuid::new_dated_at(SystemTime::now())
+    }
+
+    /// Like [`Uuid::new_dated`] with `now` as the current time.
+    pub fn new_dated_at(now: SystemTime) -> Result<Self, UuidError> {
+        let days = (unix_ms(now)? / (24 * 60 * 60 * 1000)) as i64;
+        let (year, month, day) = civil_from_days(days);
+        if !(DATED_FIRST_YEAR..DATED_FIRST_YEAR + 100).contains(&year) {
+            return Err(UuidError::InvalidTimestamp);
+        }
+        let digits = format!("{:02}{:02}{:02}", year % 100, month, day);
+        let prefix = digits.bytes().fold(0u128, |prefix, digit| {
+            (prefix << 5) | (digit - b'0') as u128
+        });
+
+        let random_bits = BYTE_SIZE * 8 - 5 * DATED_PREFIX_LEN;
+        let random = Uuid::new().to_u128() & ((1 << random_bits) - 1);
+        Ok(Uuid::from_u128((prefix << random_bits) | random))
+    }
+
+    /// The date encoded by [`Uuid::new_dated`], a year between 2000 and 2099.
+    /// `None` when the leading characters aren't a valid date.
+    #[cfg(feature = "chrono")]
+    pub fn date(&self) -> Option<chrono::NaiveDate> {
+        let encoded = self.encode();
+        let prefix = std::str::from_utf8(&encoded[..DATED_PREFIX_LEN]).ok()?;
+        if !prefix.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let number = |range: std::ops::Range<usize>| prefix[range].parse::<u32>().ok();
+        chrono::NaiveDate::from_ymd_opt(
+            DATED_FIRST_YEAR as i32 + number(0..2)? as i32,
+            number(2..4)?,
+            number(4..6)?,
+        )
+    }
+
+    /// Generates a uuid that expires `ttl` from now.
+    ///
+    /// The first 6 bytes hold the big-endian unix expiry time in milliseconds (the same
+    /// position as the timestamp of [`Uuid::new_sortable`]), the remaining 9 bytes are
+    /// random. The checksum covers the expiry so editing it by hand breaks the check
+    /// character, but that is tamper evidence only: anyone can compute a new checksum.
+    /// Use a keyed construction such as `Uuid::encrypt` (the `encrypt` feature) when the
+    /// expiry must be trusted.
+    ///
+    /// Fails like [`Uuid::new_expiring_at`].
+    pub fn new_expiring(ttl: Duration) -> Result<Self, UuidError> {
+        Uuid::new_expiring_at(ttl, SystemTime::now())
+    }
+
+    /// Like [`Uuid::new_expiring`] with `now` as the current time.
+    ///
+    /// Fails with [`UuidError::InvalidTimestamp`] if `now + ttl` overflows `SystemTime`,
+    /// is before the unix epoch, or is more than 48 bits of milliseconds after it.
+    pub fn new_expiring_at(ttl: Duration, now: SystemTime) -> Result<Self, UuidError> {
+        let expiry = now.checked_add(ttl).ok_or(UuidError::InvalidTimestamp)?;
+        Uuid::sortable_at(unix_ms(expiry)?)
+    }
+
+    /// When an id generated by [`Uuid::new_expiring`] expires.
+    pub fn expires_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.timestamp_ms())
+    }
+
+    /// Whether the id has expired, see [`Uuid::is_expired_at`].
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(SystemTime::now())
+    }
+
+    /// Whether the id has expired at `now`. An id is expired from its expiry instant on.
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        now >= self.expires_at()
+    }
+
+    /// How long ago a sortable uuid was generated, see [`Uuid::age_at_with_tolerance`].
+    pub fn age(&self) -> Option<Duration> {
+        self.age_at(SystemTime::now())
+    }
+
+    /// Like [`Uuid::age`] but relative to `now`, allowing up to
+    /// [`DEFAULT_CLOCK_SKEW_TOLERANCE`] of clock skew.
+    pub fn age_at(&self, now: SystemTime) -> Option<Duration> {
+        self.age_at_with_tolerance(now, DEFAULT_CLOCK_SKEW_TOLERANCE)
+    }
+
+    /// The time elapsed between the embedded timestamp and `now`.
+    ///
+    /// A timestamp ahead of `now` by at most `tolerance` is treated as clock skew between
+    /// the generating and the checking machine and gives an age of zero. Anything further
+    /// in the future is implausible and gives `None`. Uuids carry no version tag, so this
+    /// is also how a random uuid is told apart: its leading bytes almost always decode to
+    /// a timestamp centuries away.
+    pub fn age_at_with_tolerance(&self, now: SystemTime, tolerance: Duration) -> Option<Duration> {
+        let created = UNIX_EPOCH + Duration::from_millis(self.timestamp_ms());
+        match now.duration_since(created) {
+            Ok(age) => Some(age),
+            Err(skew) if skew.duration() <= tolerance => Some(Duration::ZERO),
+            Err(_) => None,
+        }
+    }
+}
+
+/// The fields of an id from [`Uuid::new_traceable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceableParts {
+    /// Unix time in seconds when the id was generated.
+    pub timestamp: u32,
+    /// The first 3 bytes of the SHA-256 digest of the generating host's name.
+    pub host_fingerprint: [u8; TRACEABLE_HOST_SIZE],
+    /// The low 16 bits of the generating process id.
+    pub pid: u16,
+    /// The per process 24 bit counter, wrapping around.
+    pub counter: u32,
+    /// Random bytes.
+    pub random: [u8; TRACEABLE_RANDOM_SIZE],
+}
+
+impl TraceableParts {
+    /// The fingerprint that ids generated on a host named `hostname` carry, to tell
+    /// which host an id came from.
+    pub fn host_fingerprint_of(hostname: &str) -> [u8; TRACEABLE_HOST_SIZE] {
+        let digest = ring::digest::digest(&ring::digest::SHA256, hostname.as_bytes());
+        digest.as_ref()[..TRACEABLE_HOST_SIZE].try_into().unwrap()
+    }
+}
+
+// the host and process fields of traceable ids, with the counter they share
+pub(crate) struct Traceable {
+    host_fingerprint: [u8; TRACEABLE_HOST_SIZE],
+    pid: u16,
+    counter: AtomicU32,
+}
+
+impl Traceable {
+    pub(crate) fn new(hostname: &str, pid: u32, counter: u32) -> Self {
+        Traceable {
+            host_fingerprint: TraceableParts::host_fingerprint_of(hostname),
+            pid: pid as u16,
+            counter: AtomicU32::new(counter),
+        }
+    }
+
+    // this process, on first use
+    fn current() -> &'static Traceable {
+        static CURRENT: OnceLock<Traceable> = OnceLock::new();
+        CURRENT.get_or_init(|| {
+            let counter = u32::from_be_bytes(random_bytes());
+            Traceable::new(&hostname(), std::process::id(), counter)
+        })
+    }
+
+    pub(crate) fn generate(&self, timestamp: u32) -> Uuid {
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed);
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+        bytes[4..7].copy_from_slice(&self.host_fingerprint);
+        bytes[7..9].copy_from_slice(&self.pid.to_be_bytes());
+        bytes[9..12].copy_from_slice(&counter.to_be_bytes()[1..]);
+        bytes[12..].copy_from_slice(&random_bytes::<TRACEABLE_RANDOM_SIZE>());
+        Uuid::from_array(bytes)
+    }
+}
+
+// the `HOSTNAME` variable, else the kernel's hostname, else `localhost`
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+impl Uuid {
+    /// Generates an id that records where it was made, like a MongoDB ObjectId:
+    ///
+    /// - bytes 0-3: the big-endian unix time in seconds
+    /// - bytes 4-6: a fingerprint of the hostname, see [`TraceableParts::host_fingerprint_of`]
+    /// - bytes 7-8: the low 16 bits of the process id
+    /// - bytes 9-11: a 24 bit counter, starting at a random value in each process
+    /// - bytes 12-14: random
+    ///
+    /// The hostname and process id are read once per process. Ids from the same process
+    /// within one second are ordered by the counter until it wraps. The host and process
+    /// fields are public to anyone holding the id.
+    pub fn new_traceable() -> Self {
+        let seconds = now_ms() / 1000;
+        Traceable::current().generate(seconds as u32)
+    }
+
+    /// The fields of an id from [`Uuid::new_traceable`].
+    pub fn decompose(&self) -> TraceableParts {
+        let bytes = self.bytes.as_slice();
+        TraceableParts {
+            timestamp: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+            host_fingerprint: bytes[4..7].try_into().unwrap(),
+            pid: u16::from_be_bytes(bytes[7..9].try_into().unwrap()),
+            counter: u32::from_be_bytes([0, bytes[9], bytes[10], bytes[11]]),
+            random: bytes[12..].try_into().unwrap(),
+        }
+    }
+
+    /// The host fingerprint of an id from [`Uuid::new_traceable`].
+    pub fn host_fingerprint(&self) -> [u8; TRACEABLE_HOST_SIZE] {
+        self.decompose().host_fingerprint
+    }
+
+    /// The low 16 bits of the process id of an id from [`Uuid::new_traceable`].
+    pub fn pid(&self) -> u16 {
+        self.decompose().pid
+    }
+
+    /// The counter of an id from [`Uuid::new_traceable`].
+    pub fn counter(&self) -> u32 {
+        self.decompose().counter
+    }
+}
+
+/// Builds structured uuids from a timestamp, a namespace, a machine id and random bytes.
+///
+/// Components are laid out in a fixed order regardless of the order the methods are
+/// called in, each directly after the previous one:
+///
+/// - `timestamp_ms`: 6 bytes, the big-endian unix timestamp in milliseconds
+/// - `namespace`: 4 bytes, the top 4 bytes of the namespace's [`Uuid::fingerprint64`]
+/// - `machine_id`: 4 bytes
+///
+/// The remaining bytes are zero, or random when `random_bytes` was called.
+#[derive(Debug, Clone, Default)]
+pub struct UuidBuilder {
+    timestamp_ms: Option<u64>,
+    namespace: Option<[u8; 4]>,
+    machine_id: Option<[u8; 4]>,
+    random_bytes: Option<usize>,
+}
+
+impl UuidBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timestamp_ms(&mut self, millis: u64) -> &mut Self {
+        self.timestamp_ms = Some(millis);
+        self
+    }
+
+    pub fn machine_id(&mut self, machine_id: &[u8; 4]) -> &mut Self {
+        self.machine_id = Some(*machine_id);
+        self
+    }
+
+    /// Fills every byte not taken by another component with random data, failing the
+    /// build if fewer than `n` bytes are left.
+    pub fn random_bytes(&mut self, n: usize) -> &mut Self {
+        self.random_bytes = Some(n);
+        self
+    }
+
+    pub fn namespace(&mut self, namespace: &Uuid) -> &mut Self {
+        let tag = namespace.fingerprint64().to_be_bytes();
+        self.namespace = Some(tag[..4].try_into().unwrap());
+        self
+    }
+
+    pub fn build(&self) -> Result<Uuid, UuidError> {
+        let mut segments: Vec<&[u8]> = Vec::new();
+        let timestamp;
+        if let Some(millis) = self.timestamp_ms {
+            if millis >> 48 != 0 {
+                return Err(UuidError::InvalidConfiguration(format!(
+                    "timestamp {} does not fit in 48 bits",
+                    millis
+                )));
+            }
+            timestamp = millis.to_be_bytes();
+            segments.push(&timestamp[2..]);
+        }
+        if let Some(namespace) = &self.namespace {
+            segments.push(namespace);
+        }
+        if let Some(machine_id) = &self.machine_id {
+            segments.push(machine_id);
+        }
+
+        let specified: usize = segments.iter().map(|segment| segment.len()).sum();
+        let random = self.random_bytes.unwrap_or(0);
+        if specified + random > BYTE_SIZE {
+            return Err(UuidError::InvalidConfiguration(format!(
+                "{} specified and {} random bytes do not fit in {} bytes",
+                specified, random, BYTE_SIZE
+            )));
+        }
+
+        let mut bytes = [0u8; BYTE_SIZE];
+        if self.random_bytes.is_some() {
+            rng()
+                .fill(&mut bytes[specified..])
+                .map_err(|_| UuidError::RngExhausted)?;
+        }
+        let mut offset = 0;
+        for segment in segments {
+            bytes[offset..offset + segment.len()].copy_from_slice(segment);
+            offset += segment.len();
+        }
+        Ok(Uuid::from_array(bytes))
+    }
+}
+
+// Aho-Corasick automaton compiled to a full DFA over bytes, so that scanning an
+// encoded id is one table lookup per character
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[u32; 256]>,
+    accepting: Vec<bool>,
+}
+
+impl Automaton {
+    fn new(patterns: &[&str]) -> Self {
+        let mut transitions = vec![[0u32; 256]];
+        let mut accepting = vec![false];
+        // build the trie, 0 in a transition meaning "no edge" until failure links fill it
+        for pattern in patterns.iter().filter(|p| !p.is_empty()) {
+            let mut state = 0;
+            for byte in pattern.to_ascii_uppercase().bytes() {
+                let next = transitions[state][byte as usize] as usize;
+                state = if next == 0 {
+                    transitions.push([0; 256]);
+                    accepting.push(false);
+                    let next = transitions.len() - 1;
+                    transitions[state][byte as usize] = next as u32;
+                    next
+                } else {
+                    next
+                };
+            }
+            accepting[state] = true;
+        }
+
+        // breadth first, every state's failure target is complete before it is needed
+        let mut failure = vec![0usize; transitions.len()];
+        let mut queue: std::collections::VecDeque<usize> = transitions[0]
+            .iter()
+            .filter(|next| **next != 0)
+            .map(|next| *next as usize)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            accepting[state] |= accepting[failure[state]];
+            let fallback = transitions[failure[state]];
+            for (next, fallback) in transitions[state].iter_mut().zip(fallback) {
+                if *next == 0 {
+                    *next = fallback;
+                } else {
+                    failure[*next as usize] = fallback as usize;
+                    queue.push_back(*next as usize);
+                }
+            }
+        }
+        Self {
+            transitions,
+            accepting,
+        }
+    }
+
+    fn is_match(&self, haystack: &[u8]) -> bool {
+        let mut state = 0;
+        haystack.iter().any(|byte| {
+            state = self.transitions[state][*byte as usize] as usize;
+            self.accepting[state]
+        })
+    }
+}
+
+const FILTER_MAX_ATTEMPTS: u32 = 16;
+
+/// Generates random uuids whose encoding, checksum included, contains none of a
+/// blocklist of substrings, compared case-insensitively. Empty patterns are ignored.
+#[derive(Debug)]
+pub struct FilteredGenerator {
+    automaton: Automaton,
+    regenerations: AtomicU64,
+}
+
+impl FilteredGenerator {
+    pub fn new(blocklist: &[&str]) -> Self {
+        Self {
+            automaton: Automaton::new(blocklist),
+            regenerations: AtomicU64::new(0),
+        }
+    }
+
+    /// Generates a uuid, regenerating up to 16 attempts in total before giving up
+    /// with [`UuidError::FilterExhausted`].
+    pub fn generate(&self) -> Result<Uuid, UuidError> {
+        for attempt in 0..FILTER_MAX_ATTEMPTS {
+            if attempt > 0 {
+                self.regenerations.fetch_add(1, Ordering::Relaxed);
+            }
+            let uuid = Uuid::generate_secure()?;
+            if !self.automaton.is_match(&uuid.encode()) {
+                return Ok(uuid);
+            }
+        }
+        Err(UuidError::FilterExhausted(FILTER_MAX_ATTEMPTS))
+    }
+
+    /// How many ids have been discarded and regenerated so far.
+    pub fn regenerations(&self) -> u64 {
+        self.regenerations.load(Ordering::Relaxed)
+    }
+}
+
+/// Wraps a source of uuids and refuses to hand out the same id twice, remembering the
+/// last `capacity` ids issued. Older ids are forgotten so memory stays bounded.
+pub struct UniqueGenerator<F = fn() -> Result<Uuid, UuidError>> {
+    source: F,
+    capacity: usize,
+    max_attempts: u32,
+    issued: HashSet<Uuid>,
+    order: VecDeque<Uuid>,
+    collisions: u64,
+}
+
+impl UniqueGenerator {
+    /// Guards [`Uuid::generate_secure`].
+    pub fn new(capacity: usize) -> Self {
+        UniqueGenerator::with_source(Uuid::generate_secure, capacity)
+    }
+}
+
+impl<F> UniqueGenerator<F>
+where
+    F: FnMut() -> Result<Uuid, UuidError>,
+{
+    pub fn with_source(source: F, capacity: usize) -> Self {
+        Self {
+            source,
+            capacity,
+            max_attempts: UNIQUE_MAX_ATTEMPTS,
+            issued: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            collisions: 0,
+        }
+    }
+
+    /// Sets how many ids are drawn per call before giving up, 16 by default.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Draws ids from the source until one hasn't been issued yet, failing with
+    /// [`UuidError::UniquenessExhausted`] after the configured number of attempts.
+    pub fn generate(&mut self) -> Result<Uuid, UuidError> {
+        for _ in 0..self.max_attempts {
+            let uuid = (self.source)()?;
+            if self.issued.contains(&uuid) {
+                self.collisions += 1;
+                continue;
+            }
+            if self.capacity > 0 {
+                if self.order.len() == self.capacity {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.issued.remove(&oldest);
+                    }
+                }
+                self.issued.insert(uuid);
+                self.order.push_back(uuid);
+            }
+            return Ok(uuid);
+        }
+        Err(UuidError::UniquenessExhausted(self.max_attempts))
+    }
+
+    /// How many duplicate ids have been detected and discarded so far.
+    pub fn collisions(&self) -> u64 {
+        self.collisions
+    }
+
+    /// How many issued ids are currently remembered, at most the configured capacity.
+    pub fn remembered(&self) -> usize {
+        self.order.len()
+    }
+}
+
+type RngSource = Arc<Mutex<dyn FnMut(&mut [u8]) -> Result<(), UuidError> + Send>>;
+type Clock = Arc<dyn Fn() -> u64 + Send + Sync>;
+type Hook = Arc<dyn Fn(&Uuid) + Send + Sync>;
+
+/// What a sortable [`Generator`] does when the clock reads earlier than the timestamp of
+/// the last id, e.g. after an NTP step or a VM migration. Ids within one millisecond are
+/// only ordered by a [`GeneratorBuilder::monotonic`] generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockRollbackPolicy {
+    /// Fails with [`UuidError::ClockRolledBack`] until the clock catches up.
+    Error,
+    /// Keeps the last timestamp and increments the last id's random bytes, so ids keep
+    /// sorting after the ones already issued, at the cost of their randomness.
+    #[default]
+    FreezeAndIncrement,
+    /// Uses the earlier time, so new ids can sort before ones already issued.
+    Allow,
+}
+
+/// Configures a [`Generator`]. The default configuration generates the same random ids
+/// as [`Uuid::new`].
+///
+/// Sortable ids hold a 6 byte timestamp, then the 2 byte node id if one is set, then
+/// random bytes.
+#[derive(Clone, Default)]
+pub struct GeneratorBuilder {
+    sortable: bool,
+    monotonic: bool,
+    rollback: ClockRollbackPolicy,
+    rng: Option<RngSource>,
+    clock: Option<Clock>,
+    node_id: Option<u16>,
+    filter: Option<Vec<String>>,
+    hooks: Vec<Hook>,
+}
+
+impl GeneratorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefixes ids with the timestamp from the clock, like [`Uuid::new_sortable`].
+    pub fn sortable(&mut self, sortable: bool) -> &mut Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Keeps sortable ids strictly increasing within a millisecond: while the clock reads
+    /// the last id's timestamp, the next id increments the last one's random bytes
+    /// instead of drawing new ones. Requires `sortable`. See [`Generator::snapshot`] to
+    /// carry on across restarts, and [`GeneratorBuilder::clock_rollback`] for clocks
+    /// that go backwards.
+    pub fn monotonic(&mut self, monotonic: bool) -> &mut Self {
+        self.monotonic = monotonic;
+        self
+    }
+
+    /// What to do when the clock goes backwards, [`ClockRollbackPolicy::FreezeAndIncrement`]
+    /// by default. Only sortable generators read the clock.
+    pub fn clock_rollback(&mut self, policy: ClockRollbackPolicy) -> &mut Self {
+        self.rollback = policy;
+        self
+    }
+
+    /// Draws random bytes from `source` instead of the system RNG.
+    pub fn rng<F>(&mut self, source: F) -> &mut Self
+    where
+        F: FnMut(&mut [u8]) -> Result<(), UuidError> + Send + 'static,
+    {
+        self.rng = Some(Arc::new(Mutex::new(source)));
+        self
+    }
+
+    /// Reads unix milliseconds from `clock` instead of the system time. Requires
+    /// `sortable`.
+    pub fn clock<F>(&mut self, clock: F) -> &mut Self
+    where
+        F: Fn() -> u64 + Send + Sync + 'static,
+    {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Stamps the node id after the timestamp. Requires `sortable`.
+    pub fn node_id(&mut self, node_id: u16) -> &mut Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Regenerates ids containing any of `blocklist`, like a [`FilteredGenerator`].
+    pub fn filter(&mut self, blocklist: &[&str]) -> &mut Self {
+        self.filter = Some(blocklist.iter().map(|p| p.to_string()).collect());
+        self
+    }
+
+    /// Calls `hook` with every id the generator returns, once per id in a batch too.
+    /// Hooks run in registration order. A panicking hook is caught and ignored, so it
+    /// can't fail generation or poison the generator; the panic message is still
+    /// printed by the panic handler.
+    pub fn hook<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&Uuid) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    pub fn build(&self) -> Result<Generator, UuidError> {
+        if !self.sortable {
+            let unused = [
+                (self.clock.is_some(), "clock"),
+                (self.node_id.is_some(), "node_id"),
+                (self.monotonic, "monotonic"),
+            ];
+            if let Some((_, option)) = unused.iter().find(|(set, _)| *set) {
+                return Err(UuidError::InvalidConfiguration(format!(
+                    "{} requires a sortable generator",
+                    option
+                )));
+            }
+        }
+        let filter = self.filter.as_ref().map(|blocklist| {
+            let blocklist: Vec<&str> = blocklist.iter().map(String::as_str).collect();
+            Automaton::new(&blocklist)
+        });
+        Ok(Generator {
+            sortable: self.sortable,
+            monotonic: self.monotonic,
+            rollback: self.rollback,
+            rng: self.rng.clone(),
+            clock: self.clock.clone(),
+            node_id: self.node_id,
+            filter,
+            hooks: self.hooks.clone(),
+            last: None,
+        })
+    }
+
+    /// Builds a monotonic generator that carries on after `state`, from
+    /// [`Generator::snapshot`]. The builder must be sortable and monotonic with the same
+    /// node id as the state, otherwise this fails with
+    /// [`UuidError::InvalidConfiguration`], as does a state whose random bytes don't fit
+    /// that layout. A state more than [`DEFAULT_CLOCK_SKEW_TOLERANCE`] ahead of the clock
+    /// fails with [`UuidError::InvalidTimestamp`], rather than stalling the clock there.
+    pub fn restore(&self, state: &GeneratorState) -> Result<Generator, UuidError> {
+        if !self.sortable || !self.monotonic {
+            return Err(UuidError::InvalidConfiguration(
+                "restoring requires a sortable, monotonic generator".to_string(),
+            ));
+        }
+        if state.node_id != self.node_id {
+            return Err(UuidError::InvalidConfiguration(format!(
+                "state node id {:?} does not match the generator's {:?}",
+                state.node_id, self.node_id
+            )));
+        }
+        let offset = 6 + self.node_id.map_or(0, |_| NODE_ID_SIZE);
+        if state.last_entropy.len() != BYTE_SIZE - offset {
+            return Err(UuidError::InvalidConfiguration(format!(
+                "state has {} random bytes, the generator {}",
+                state.last_entropy.len(),
+                BYTE_SIZE - offset
+            )));
+        }
+        if state.last_timestamp_ms >> SORTABLE_TIMESTAMP_BITS != 0 {
+            return Err(UuidError::InvalidTimestamp);
+        }
+        let now = self.clock.as_ref().map_or_else(now_ms, |clock| clock());
+        let tolerance = DEFAULT_CLOCK_SKEW_TOLERANCE.as_millis() as u64;
+        if state.last_timestamp_ms > now.saturating_add(tolerance) {
+            return Err(UuidError::InvalidTimestamp);
+        }
+
+        let mut generator = self.build()?;
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes[..6].copy_from_slice(&state.last_timestamp_ms.to_be_bytes()[2..]);
+        if let Some(node_id) = self.node_id {
+            bytes[6..6 + NODE_ID_SIZE].copy_from_slice(&node_id.to_be_bytes());
+        }
+        bytes[offset..].copy_from_slice(&state.last_entropy);
+        generator.last = Some(Uuid::from_array(bytes));
+        Ok(generator)
+    }
+}
+
+impl std::fmt::Debug for GeneratorBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("GeneratorBuilder")
+            .field("sortable", &self.sortable)
+            .field("monotonic", &self.monotonic)
+            .field("rollback", &self.rollback)
+            .field("rng", &self.rng.as_ref().map(|_| ".."))
+            .field("clock", &self.clock.as_ref().map(|_| ".."))
+            .field("node_id", &self.node_id)
+            .field("filter", &self.filter)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+/// Generates ids as configured by a [`GeneratorBuilder`]. Iterating yields
+/// [`Generator::generate`] forever.
+pub struct Generator {
+    sortable: bool,
+    monotonic: bool,
+    rollback: ClockRollbackPolicy,
+    rng: Option<RngSource>,
+    clock: Option<Clock>,
+    node_id: Option<u16>,
+    filter: Option<Automaton>,
+    hooks: Vec<Hook>,
+    // the last sortable id generated, filtered out or not
+    last: Option<Uuid>,
+}
+
+impl Generator {
+    pub fn generate(&mut self) -> Result<Uuid, UuidError> {
+        let attempts = if self.filter.is_some() {
+            FILTER_MAX_ATTEMPTS
+        } else {
+            1
+        };
+        for _ in 0..attempts {
+            let uuid = self.generate_unfiltered()?;
+            match &self.filter {
+                Some(filter) if filter.is_match(&uuid.encode()) => continue,
+                _ => {
+                    for hook in &self.hooks {
+                        let _ = std::panic::catch_unwind(AssertUnwindSafe(|| hook(&uuid)));
+                    }
+                    return Ok(uuid);
+                }
+            }
+        }
+        Err(UuidError::FilterExhausted(FILTER_MAX_ATTEMPTS))
+    }
+
+    pub fn batch(&mut self, n: usize) -> Result<Vec<Uuid>, UuidError> {
+        (0..n).map(|_| self.generate()).collect()
+    }
+
+    /// The timestamp and random bytes of the last sortable id, to persist and hand to
+    /// [`GeneratorBuilder::restore`] or [`Generator::restore`] after a restart. Before the
+    /// first id the timestamp is 0.
+    pub fn snapshot(&self) -> GeneratorState {
+        let offset = 6 + self.node_id.map_or(0, |_| NODE_ID_SIZE);
+        let (last_timestamp_ms, last_entropy) = match &self.last {
+            Some(last) => (
+                last.timestamp_ms(),
+                last.bytes.as_slice()[offset..].to_vec(),
+            ),
+            None => (0, vec![0; BYTE_SIZE - offset]),
+        };
+        GeneratorState {
+            last_timestamp_ms,
+            node_id: self.node_id,
+            last_entropy,
+        }
+    }
+
+    /// A sortable, monotonic generator with the state's node id, the system clock and
+    /// the system RNG, carrying on after `state`. See [`GeneratorBuilder::restore`].
+    pub fn restore(state: &GeneratorState) -> Result<Generator, UuidError> {
+        let mut builder = GeneratorBuilder::new();
+        builder.sortable(true).monotonic(true);
+        if let Some(node_id) = state.node_id {
+            builder.node_id(node_id);
+        }
+        builder.restore(state)
+    }
+
+    fn generate_unfiltered(&mut self) -> Result<Uuid, UuidError> {
+        let mut bytes = [0u8; BYTE_SIZE];
+        let mut offset = 0;
+        if self.sortable {
+            let millis = self.clock.as_ref().map_or_else(now_ms, |clock| clock());
+            if millis >> 48 != 0 {
+                return Err(UuidError::InvalidTimestamp);
+            }
+            bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+            offset = 6;
+            if let Some(node_id) = self.node_id {
+                bytes[offset..offset + NODE_ID_SIZE].copy_from_slice(&node_id.to_be_bytes());
+                offset += NODE_ID_SIZE;
+            }
+            let increment_last = match self.last.map(|last| last.timestamp_ms()) {
+                Some(last_ms) if millis < last_ms => match self.rollback {
+                    ClockRollbackPolicy::Error => {
+                        return Err(UuidError::ClockRolledBack {
+                            last_ms,
+                            now_ms: millis,
+                        })
+                    }
+                    ClockRollbackPolicy::FreezeAndIncrement => true,
+                    ClockRollbackPolicy::Allow => false,
+                },
+                Some(last_ms) => self.monotonic && millis == last_ms,
+                None => false,
+            };
+            if let Some(last) = self.last.filter(|_| increment_last) {
+                let mut next = last.to_byte_array();
+                increment(&mut next[offset..]);
+                // the random bytes wrapped around, move on to the next millisecond
+                if next[offset..].iter().all(|b| *b == 0) {
+                    increment(&mut next[..6]);
+                    if next[..6].iter().all(|b| *b == 0) {
+                        return Err(UuidError::InvalidTimestamp);
+                    }
+                }
+                let next = Uuid::from_array(next);
+                self.last = Some(next);
+                return Ok(next);
+            }
+        }
+        match &self.rng {
+            Some(rng) => {
+                (rng.lock().unwrap_or_else(PoisonError::into_inner))(&mut bytes[offset..])?
+            }
+            None => fill_from_pool(&mut bytes[offset..]).map_err(|_| UuidError::RngExhausted)?,
+        }
+        let uuid = Uuid::from_array(bytes);
+        if self.sortable {
+            self.last = Some(uuid);
+        }
+        Ok(uuid)
+    }
+}
+
+impl Iterator for Generator {
+    type Item = Result<Uuid, UuidError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.generate())
+    }
+}
+
+impl std::fmt::Debug for Generator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Generator")
+            .field("sortable", &self.sortable)
+            .field("monotonic", &self.monotonic)
+            .field("rollback", &self.rollback)
+            .field("node_id", &self.node_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where a monotonic [`Generator`] left off, from [`Generator::snapshot`]. Persist it with
+/// [`GeneratorState::save`], or with serde under the `serde` feature, so that a restarted
+/// process never goes back on ids it already issued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratorState {
+    /// The timestamp of the last id in unix milliseconds.
+    pub last_timestamp_ms: u64,
+    pub node_id: Option<u16>,
+    /// The random bytes of the last id, after its timestamp and node id.
+    pub last_entropy: Vec<u8>,
+}
+
+impl GeneratorState {
+    /// Writes the state to `path` atomically: to a uniquely named temporary file next to
+    /// it, synced, then renamed over `path`, so a crash leaves either the old or the new
+    /// state and concurrent saves don't write to the same temporary file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(format!(
+            ".{}.{:016x}.tmp",
+            std::process::id(),
+            u64::from_be_bytes(random_bytes())
+        ));
+        let temporary = PathBuf::from(temporary);
+        let written = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temporary)
+            .and_then(|mut file| {
+                io::Write::write_all(&mut file, format!("{}\n", self).as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&temporary, path));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temporary);
+        }
+        written?;
+        // the rename itself is only durable once the directory entry is synced
+        #[cfg(unix)]
+        {
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            std::fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Reads a state written by [`GeneratorState::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        std::fs::read_to_string(path)?
+            .trim()
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// `v1 <timestamp> <node id or -> <random bytes in hex>`, the format of
+/// [`GeneratorState::save`].
+impl std::fmt::Display for GeneratorState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "v1 {} ", self.last_timestamp_ms)?;
+        match self.node_id {
+            Some(node_id) => write!(f, "{} ", node_id)?,
+            None => f.write_str("- ")?,
+        }
+        for byte in &self.last_entropy {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for GeneratorState {
+    type Err = UuidError;
+
+    fn from_str(s: &str) -> Result<Self, UuidError> {
+        let invalid = || UuidError::InvalidEncoding(format!("invalid generator state {:?}", s));
+        let fields: Vec<&str> = s.split(' ').collect();
+        let [version, timestamp, node_id, entropy] = fields[..] else {
+            return Err(invalid());
+        };
+        if version != "v1" || entropy.len() % 2 != 0 || !entropy.is_ascii() {
+            return Err(invalid());
+        }
+        let node_id = match node_id {
+            "-" => None,
+            node_id => Some(node_id.parse().map_err(|_| invalid())?),
+        };
+        let last_entropy = (0..entropy.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&entropy[i..i + 2], 16))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid())?;
+        Ok(GeneratorState {
+            last_timestamp_ms: timestamp.parse().map_err(|_| invalid())?,
+            node_id,
+            last_entropy,
+        })
+    }
+}
+
+/// How [`write_ids`] formats each id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOpts {
+    /// Writes lower case characters.
+    pub lowercase: bool,
+    /// Leaves out the trailing checksum character.
+    pub omit_checksum: bool,
+    /// Written after every id, including the last. Defaults to `"\n"`.
+    pub separator: String,
+}
+
+impl Default for WriteOpts {
+    fn default() -> Self {
+        WriteOpts {
+            lowercase: false,
+            omit_checksum: false,
+            separator: "\n".to_string(),
+        }
+    }
+}
+
+/// Writes `count` random ids to `w`, each followed by `opts.separator`, e.g. to seed a
+/// load test. Ids are drawn in batches from a keystream seeded by the system RNG and
+/// encoded into a reused buffer, which is much faster than formatting and writing them
+/// one by one. Scoped and default generators are not used. `w` is not flushed.
+pub fn write_ids<W: io::Write>(w: &mut W, count: u64, opts: WriteOpts) -> io::Result<()> {
+    let id_len = if opts.omit_checksum {
+        ENCODED_LEN - 1
+    } else {
+        ENCODED_LEN
+    };
+    let separator = opts.separator.as_bytes();
+    // an AES-256-GCM keystream under a fresh random key is far cheaper per byte than
+    // the system RNG, which dominates the cost otherwise
+    let mut key = [0u8; 32];
+    rng()
+        .fill(&mut key)
+        .map_err(|_| io::Error::other(UuidError::RngExhausted))?;
+    let key = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_256_GCM, &key).expect("32 bytes is an AES-256 key"),
+    );
+    let mut random = vec![0u8; WRITE_BATCH_SIZE * BYTE_SIZE];
+    let mut buffer = Vec::with_capacity(WRITE_BATCH_SIZE * (id_len + separator.len()));
+
+    let mut remaining = count;
+    let mut batch_index = 0u64;
+    while remaining > 0 {
+        let batch = remaining.min(WRITE_BATCH_SIZE as u64) as usize;
+        let random = &mut random[..batch * BYTE_SIZE];
+        random.fill(0);
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        nonce[4..].copy_from_slice(&batch_index.to_be_bytes());
+        // only the keystream is wanted, not the tag
+        let _tag = key
+            .seal_in_place_separate_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                random,
+            )
+            .map_err(|_| io::Error::other(UuidError::RngExhausted))?;
+        batch_index += 1;
+
+        buffer.clear();
+        for bytes in random.chunks_exact(BYTE_SIZE) {
+            let encoded = encode_fast(bytes.try_into().unwrap());
+            buffer.extend_from_slice(&encoded[..id_len]);
+            buffer.extend_from_slice(separator);
+        }
+        if opts.lowercase {
+            // the separator is the caller's, leave it as is
+            for id in buffer.chunks_exact_mut(id_len + separator.len()) {
+                id[..id_len].make_ascii_lowercase();
+            }
+        }
+        w.write_all(&buffer)?;
+        remaining -= batch as u64;
+    }
+    Ok(())
+}
+
+// `Uuid::encode` on two 60 bit halves, which avoids 128 bit arithmetic and the byte
+// by byte checksum
+fn encode_fast(bytes: [u8; BYTE_SIZE]) -> [u8; ENCODED_LEN] {
+    let high = u64::from_be_bytes(bytes[..8].try_into().unwrap()) >> 4;
+    let low = u64::from_be_bytes(bytes[7..].try_into().unwrap()) & ((1 << 60) - 1);
+
+    let mut encoded = [0u8; ENCODED_LEN];
+    for i in 0..12 {
+        let shift = 5 * (11 - i);
+        encoded[i] = CROCKFORD_CHARS[((high >> shift) & 0x1f) as usize];
+        encoded[12 + i] = CROCKFORD_CHARS[((low >> shift) & 0x1f) as usize];
+    }
+    // the value is high * 2^60 + low, and 2^60 is 10 modulo 37
+    let modulo = CHECKSUM_MODULO as u64;
+    let checksum = ((high % modulo) * 10 + low % modulo) % modulo;
+    debug_assert_eq!(checksum as u8, Bytes(bytes).derive_crockford_checksum());
+    encoded[ENCODED_LEN - 1] = CROCKFORD_CHECKSUM_CHARS.as_bytes()[checksum as usize];
+    encoded
+}
@@ -0,0 +1,170 @@
+#[cfg(feature = "concurrent-interner")]
+use crate::error::UuidError;
+use crate::uuid::Uuid;
+use std::collections::HashMap;
+
+#[cfg(feature = "concurrent-interner")]
+pub(crate) const SHARD_BITS: u32 = 4;
+// local handles are shifted left by SHARD_BITS, so each shard has fewer of them
+#[cfg(feature = "concurrent-interner")]
+pub(crate) const SHARD_CAPACITY: usize = (u32::MAX >> SHARD_BITS) as usize + 1;
+
+/// A handle to a uuid stored in a [`UuidInterner`], 4 bytes instead of 16.
+///
+/// Handles are only meaningful to the interner that issued them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InternedUuid(u32);
+
+impl InternedUuid {
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Deduplicates repeated uuids into [`InternedUuid`] handles.
+#[derive(Debug, Default, Clone)]
+pub struct UuidInterner {
+    handles: HashMap<Uuid, InternedUuid>,
+    arena: Vec<Uuid>,
+}
+
+impl UuidInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            handles: HashMap::with_capacity(capacity),
+            arena: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The handle of `uuid`, storing it first if it hasn't been seen yet.
+    ///
+    /// # Panics
+    ///
+    /// If more than `u32::MAX` distinct uuids are interned.
+    pub fn intern(&mut self, uuid: &Uuid) -> InternedUuid {
+        if let Some(handle) = self.handles.get(uuid) {
+            return *handle;
+        }
+        let index = u32::try_from(self.arena.len()).expect("interner is full");
+        let handle = InternedUuid(index);
+        self.arena.push(*uuid);
+        self.handles.insert(*uuid, handle);
+        handle
+    }
+
+    /// The handle of `uuid` if it has been interned.
+    pub fn get(&self, uuid: &Uuid) -> Option<InternedUuid> {
+        self.handles.get(uuid).copied()
+    }
+
+    /// # Panics
+    ///
+    /// If `handle` was issued by another interner and is out of range.
+    pub fn resolve(&self, handle: InternedUuid) -> &Uuid {
+        &self.arena[handle.0 as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+
+    /// How many uuids can be interned before the storage reallocates.
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity().min(self.handles.capacity())
+    }
+
+    /// The interned uuids in the order they were first seen.
+    pub fn iter(&self) -> impl Iterator<Item = (InternedUuid, &Uuid)> {
+        self.arena
+            .iter()
+            .enumerate()
+            .map(|(index, uuid)| (InternedUuid(index as u32), uuid))
+    }
+}
+
+/// A [`UuidInterner`] that can be shared between threads, spreading uuids over 16
+/// independently locked shards.
+#[cfg(feature = "concurrent-interner")]
+#[derive(Debug, Default)]
+pub struct ConcurrentUuidInterner {
+    shards: [std::sync::RwLock<UuidInterner>; 1 << SHARD_BITS],
+}
+
+#[cfg(feature = "concurrent-interner")]
+impl ConcurrentUuidInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn shard(uuid: &Uuid) -> usize {
+        // the low bits of the hash, since the leading bytes of sortable ids barely vary
+        (uuid.fingerprint64() & ((1 << SHARD_BITS) - 1)) as usize
+    }
+
+    /// The handle of `uuid`, storing it first if it hasn't been seen yet. Fails with
+    /// [`UuidError::InternerFull`] once its shard holds `2^28` distinct uuids.
+    pub fn intern(&self, uuid: &Uuid) -> Result<InternedUuid, UuidError> {
+        let shard = Self::shard(uuid);
+        if let Some(handle) = self.shards[shard].read().unwrap().get(uuid) {
+            return Ok(Self::handle(shard, handle));
+        }
+        let mut interner = self.shards[shard].write().unwrap();
+        if let Some(handle) = interner.get(uuid) {
+            return Ok(Self::handle(shard, handle));
+        }
+        if interner.len() >= SHARD_CAPACITY {
+            return Err(UuidError::InternerFull);
+        }
+        Ok(Self::handle(shard, interner.intern(uuid)))
+    }
+
+    fn handle(shard: usize, local: InternedUuid) -> InternedUuid {
+        InternedUuid(local.0 << SHARD_BITS | shard as u32)
+    }
+
+    /// The handle of `uuid` if it has been interned.
+    pub fn get(&self, uuid: &Uuid) -> Option<InternedUuid> {
+        let shard = Self::shard(uuid);
+        let handle = self.shards[shard].read().unwrap().get(uuid)?;
+        Some(Self::handle(shard, handle))
+    }
+
+    /// # Panics
+    ///
+    /// If `handle` was issued by another interner and is out of range.
+    pub fn resolve(&self, handle: InternedUuid) -> Uuid {
+        let shard = (handle.0 & ((1 << SHARD_BITS) - 1)) as usize;
+        let local = InternedUuid(handle.0 >> SHARD_BITS);
+        *self.shards[shard].read().unwrap().resolve(local)
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.read().unwrap().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// A snapshot of the interned uuids, grouped by shard.
+    pub fn to_vec(&self) -> Vec<(InternedUuid, Uuid)> {
+        let mut entries = Vec::with_capacity(self.len());
+        for (shard, interner) in self.shards.iter().enumerate() {
+            let interner = interner.read().unwrap();
+            entries.extend(
+                interner
+                    .iter()
+                    .map(|(handle, uuid)| (Self::handle(shard, handle), *uuid)),
+            );
+        }
+        entries
+    }
+}
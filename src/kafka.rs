@@ -0,0 +1,49 @@
+use crate::encode::UuidString;
+use crate::error::UuidError;
+use crate::uuid::{Uuid, BYTE_SIZE};
+use rdkafka::message::ToBytes;
+
+/// The 15 raw bytes, so that topics are partitioned by the id itself.
+impl ToBytes for Uuid {
+    fn to_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+/// A Kafka key holding the canonical string of a uuid, for topics that need human
+/// readable keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyString(UuidString);
+
+impl KeyString {
+    pub fn new(uuid: &Uuid) -> Self {
+        KeyString(uuid.to_string_stack())
+    }
+}
+
+impl From<Uuid> for KeyString {
+    fn from(uuid: Uuid) -> Self {
+        KeyString::new(&uuid)
+    }
+}
+
+impl ToBytes for KeyString {
+    fn to_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Uuid {
+    /// Parses a message key written either as the 15 raw bytes or as a [`KeyString`].
+    ///
+    /// The two forms are told apart by length alone: a 15 byte key is always raw
+    /// bytes, even if it happens to be printable, and a 25 byte key is always the
+    /// string form, checksum verified.
+    pub fn from_kafka_key(key: &[u8]) -> Result<Self, UuidError> {
+        match key.len() {
+            BYTE_SIZE => Uuid::from_slice(key),
+            len if len == Uuid::encoded_length_with_checksum() => Uuid::from_ascii(key),
+            len => Err(UuidError::InvalidByteLength(len)),
+        }
+    }
+}
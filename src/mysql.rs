@@ -0,0 +1,103 @@
+use crate::error::UuidError;
+use crate::uuid::{Uuid, BYTE_SIZE};
+use mysql_common::value::convert::{FromValue, FromValueError};
+use mysql_common::value::Value;
+
+/// The canonical 25 character string, for `CHAR(25)` and `VARCHAR` columns.
+impl From<Uuid> for Value {
+    fn from(uuid: Uuid) -> Self {
+        Value::Bytes(uuid.to_string_stack().as_bytes().to_vec())
+    }
+}
+
+/// A MySQL value that isn't a uuid, handed back together with the reason.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MysqlValueError {
+    pub value: Value,
+    pub source: UuidError,
+}
+
+impl std::fmt::Display for MysqlValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "the value {:?} is not a uuid: {}",
+            self.value, self.source
+        )
+    }
+}
+
+impl std::error::Error for MysqlValueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<MysqlValueError> for FromValueError {
+    fn from(err: MysqlValueError) -> Self {
+        FromValueError(err.value)
+    }
+}
+
+/// See [`Uuid::from_mysql_value`].
+impl TryFrom<Value> for Uuid {
+    type Error = MysqlValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match Uuid::from_mysql_value(&value) {
+            Ok(uuid) => Ok(uuid),
+            Err(source) => Err(MysqlValueError { value, source }),
+        }
+    }
+}
+
+/// `mysql_common` requires the intermediate of [`FromValue`] to fail with a bare
+/// [`FromValueError`], so `from_value_opt` can only hand the value back; convert with
+/// `Uuid::try_from(value)` to keep the [`UuidError`] as well.
+impl FromValue for Uuid {
+    type Intermediate = UuidIr;
+}
+
+/// The intermediate of `FromValue for Uuid`, not meant to be named. Keeps the original
+/// value, like `mysql_common`'s own `ParseIr`, so rows can be handed back intact.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct UuidIr(Uuid, Value);
+
+impl TryFrom<Value> for UuidIr {
+    type Error = FromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match Uuid::from_mysql_value(&value) {
+            Ok(uuid) => Ok(UuidIr(uuid, value)),
+            Err(_) => Err(FromValueError(value)),
+        }
+    }
+}
+
+impl From<UuidIr> for Uuid {
+    fn from(ir: UuidIr) -> Self {
+        ir.0
+    }
+}
+
+impl From<UuidIr> for Value {
+    fn from(ir: UuidIr) -> Self {
+        ir.1
+    }
+}
+
+impl Uuid {
+    /// Reads a MySQL value holding either the 15 raw bytes of a `BINARY(15)` column or
+    /// the string of a text column, told apart by length like [`Uuid::from_kafka_key`].
+    pub fn from_mysql_value(value: &Value) -> Result<Self, UuidError> {
+        match value {
+            Value::Bytes(bytes) if bytes.len() == BYTE_SIZE => Uuid::from_slice(bytes),
+            Value::Bytes(bytes) => Uuid::from_ascii(bytes),
+            value => Err(UuidError::InvalidEncoding(format!(
+                "expected a string or binary value, got {:?}",
+                value
+            ))),
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use crate::encode::{
+    custom_checksum_alphabet, custom_checksum_char, CROCKFORD_CHARS, CROCKFORD_CHECKSUM_CHARS,
+    ENCODED_LEN,
+};
+use crate::error::UuidError;
+use crate::uuid::{Uuid, UuidRange};
+
+pub(crate) const INVALID: u8 = 0xff;
+
+// ascii byte to its 5 bit value, case-insensitive, with `I`, `L` and `O` accepted as
+// aliases of `1`, `1` and `0`
+pub(crate) const BODY_VALUES: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < CROCKFORD_CHARS.len() {
+        table[CROCKFORD_CHARS[i] as usize] = i as u8;
+        table[CROCKFORD_CHARS[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    let mut aliases: &[(u8, u8)] = &[(b'I', 1), (b'L', 1), (b'O', 0)];
+    while let [(alias, value), rest @ ..] = aliases {
+        table[*alias as usize] = *value;
+        table[alias.to_ascii_lowercase() as usize] = *value;
+        aliases = rest;
+    }
+    table
+};
+
+// ascii byte to its checksum value, case-insensitive and without aliases
+const CHECK_VALUES: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let chars = CROCKFORD_CHECKSUM_CHARS.as_bytes();
+    let mut i = 0;
+    while i < chars.len() {
+        table[chars[i] as usize] = i as u8;
+        table[chars[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+impl Uuid {
+    /// Parses the 25 character encoding directly from ASCII bytes, e.g. a slice of a
+    /// network buffer, without a separate UTF-8 validation pass. The rules and errors
+    /// are exactly those of parsing a `&str`.
+    pub fn from_ascii(bytes: &[u8]) -> Result<Self, UuidError> {
+        if let Some(position) = bytes.iter().position(|b| !b.is_ascii()) {
+            let byte = bytes[position];
+            return Err(UuidError::InvalidCharacter { position, byte });
+        }
+        if bytes.len() != Uuid::encoded_length_with_checksum() {
+            return Err(UuidError::InvalidLength(bytes.len()));
+        }
+
+        let (body, check) = bytes.split_at(Uuid::encoded_length());
+        let mut value = 0u128;
+        for (position, byte) in body.iter().enumerate() {
+            match BODY_VALUES[*byte as usize] {
+                INVALID => {
+                    return Err(UuidError::InvalidCharacter {
+                        position,
+                        byte: *byte,
+                    })
+                }
+                digit => value = (value << 5) | digit as u128,
+            }
+        }
+        let check = match CHECK_VALUES[check[0] as usize] {
+            INVALID => {
+                return Err(UuidError::InvalidCharacter {
+                    position: Uuid::encoded_length(),
+                    byte: check[0],
+                })
+            }
+            check => check,
+        };
+
+        let uuid = Uuid::from_u128(value);
+        if uuid.checksum == check {
+            Ok(uuid)
+        } else {
+            Err(UuidError::ChecksumMismatch)
+        }
+    }
+
+    /// The range of uuids whose encoding starts with `prefix`, for turning a partial id
+    /// into `WHERE id >= start AND id < end`. The prefix is case-insensitive, accepts the
+    /// `I`, `L` and `O` aliases and is trimmed of surrounding whitespace. It can be up to
+    /// the 24 characters of the id without its checksum; an empty prefix matches every id.
+    pub fn range_for_prefix(prefix: &str) -> Result<UuidRange, UuidError> {
+        let start = prefix.len() - prefix.trim_start().len();
+        let prefix = prefix.trim().as_bytes();
+        if prefix.len() > Uuid::encoded_length() {
+            return Err(UuidError::InvalidLength(prefix.len()));
+        }
+
+        let mut value = 0u128;
+        for (position, byte) in prefix.iter().enumerate() {
+            match BODY_VALUES[*byte as usize] {
+                INVALID => {
+                    return Err(UuidError::InvalidCharacter {
+                        position: start + position,
+                        byte: *byte,
+                    })
+                }
+                digit => value = (value << 5) | digit as u128,
+            }
+        }
+
+        // 24 characters hold exactly 120 bits, so the unused characters are whole 5 bit groups
+        let shift = 5 * (Uuid::encoded_length() - prefix.len()) as u32;
+        let end = value + 1;
+        let end = (end < 1 << (5 * prefix.len())).then(|| Uuid::from_u128(end << shift));
+        Ok(UuidRange::new(Uuid::from_u128(value << shift), end))
+    }
+
+    /// Like [`Uuid::from_ascii`], but also accepts ids split up with hyphens, as the
+    /// Crockford spec allows, and surrounded by ASCII whitespace, e.g. `4S0Y2-VZ7SF-...`
+    /// pasted from a document. Errors report positions in the original input.
+    pub fn from_ascii_lenient(bytes: &[u8]) -> Result<Self, UuidError> {
+        let start = bytes.len() - bytes.trim_ascii_start().len();
+        let trimmed = bytes.trim_ascii();
+
+        let mut compact = [0u8; ENCODED_LEN];
+        let mut positions = [0usize; ENCODED_LEN];
+        let mut len = 0;
+        for (offset, byte) in trimmed.iter().enumerate() {
+            if *byte == b'-' {
+                continue;
+            }
+            if len < ENCODED_LEN {
+                compact[len] = *byte;
+                positions[len] = start + offset;
+            }
+            len += 1;
+        }
+        if let Some(position) = trimmed.iter().position(|b| !b.is_ascii()) {
+            let byte = trimmed[position];
+            return Err(UuidError::InvalidCharacter {
+                position: start + position,
+                byte,
+            });
+        }
+        if len != ENCODED_LEN {
+            return Err(UuidError::InvalidLength(len));
+        }
+
+        Uuid::from_ascii(&compact).map_err(|e| match e {
+            UuidError::InvalidCharacter { position, byte } => UuidError::InvalidCharacter {
+                position: positions[position],
+                byte,
+            },
+            e => e,
+        })
+    }
+
+    /// Parses the output of [`Uuid::display_with_rotated_alphabet`] with the same
+    /// `rotation`.
+    pub fn parse_with_rotated_alphabet(s: &str, rotation: usize) -> Result<Self, UuidError> {
+        let bytes = s.as_bytes();
+        let mut unrotated = [0u8; ENCODED_LEN];
+        if bytes.len() != ENCODED_LEN {
+            return Uuid::from_ascii(bytes);
+        }
+        unrotated.copy_from_slice(bytes);
+        let last = ENCODED_LEN - 1;
+        // an invalid checksum character is left for `from_ascii` to report
+        let check = CHECK_VALUES[bytes[last] as usize];
+        if check != INVALID {
+            let modulo = CROCKFORD_CHECKSUM_CHARS.len();
+            let index = (check as usize + modulo - rotation % modulo) % modulo;
+            unrotated[last] = CROCKFORD_CHECKSUM_CHARS.as_bytes()[index];
+        }
+        Uuid::from_ascii(&unrotated)
+    }
+
+    /// Whether `s` is a 24 character body followed by its checksum over `alphabet`, as
+    /// written by [`Uuid::to_string_with_checksum_alphabet`]. The body is read as usual;
+    /// the checksum character must match exactly. Always false for an invalid alphabet.
+    pub fn is_valid_with_custom_alphabet(s: &str, alphabet: &str) -> bool {
+        let Ok(alphabet) = custom_checksum_alphabet(alphabet) else {
+            return false;
+        };
+        let Some(check) = s.chars().next_back() else {
+            return false;
+        };
+        let body = &s.as_bytes()[..s.len() - check.len_utf8()];
+        if body.len() != Uuid::encoded_length() {
+            return false;
+        }
+        let mut value = 0u128;
+        for byte in body {
+            match BODY_VALUES[*byte as usize] {
+                INVALID => return false,
+                digit => value = (value << 5) | digit as u128,
+            }
+        }
+        custom_checksum_char(value, &alphabet) == check
+    }
+
+    /// Parses every token of `input` separated by commas, spaces, tabs or newlines.
+    pub fn parse_multiple(input: &str) -> Vec<Result<Self, UuidError>> {
+        tokens(input).map(Uuid::try_from).collect()
+    }
+
+    /// [`Uuid::parse_multiple`], failing with the first token that doesn't parse.
+    pub fn parse_multiple_strict(input: &str) -> Result<Vec<Self>, UuidError> {
+        tokens(input).map(Uuid::try_from).collect()
+    }
+
+    /// The uuids among the tokens of `text`, skipping anything that doesn't parse.
+    pub fn extract_uuids(text: &str) -> Vec<Self> {
+        tokens(text)
+            .filter_map(|t| Uuid::try_from(t).ok())
+            .collect()
+    }
+
+    /// The byte offset of the first occurrence of the uuid's canonical upper case
+    /// encoding in `haystack`.
+    pub fn find_in(&self, haystack: &str) -> Option<usize> {
+        haystack.find(self.to_string_stack().as_str())
+    }
+}
+
+fn tokens(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split([',', '\n', '\r', '\t', ' '])
+        .filter(|token| !token.is_empty())
+}
+
+impl TryFrom<&str> for Uuid {
+    type Error = UuidError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::from_ascii(value.as_bytes())
+    }
+}
+
+impl TryFrom<String> for Uuid {
+    type Error = UuidError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Uuid::from_ascii(value.as_bytes())
+    }
+}
+
+impl PartialEq<String> for Uuid {
+    fn eq(&self, other: &String) -> bool {
+        // equivalent to parsing `other` and comparing, but bails on the first mismatch
+        let other = other.as_bytes();
+        if other.len() != Uuid::encoded_length_with_checksum() {
+            return false;
+        }
+
+        let encoded = self.encode();
+        let (id, check) = other.split_at(Uuid::encoded_length());
+        let id_matches =
+            id.iter()
+                .zip(encoded.iter())
+                .all(|(c, expected)| match BODY_VALUES[*c as usize] {
+                    INVALID => false,
+                    value => CROCKFORD_CHARS[value as usize] == *expected,
+                });
+
+        id_matches && check[0].to_ascii_uppercase() == encoded[Uuid::encoded_length()]
+    }
+}
+
+/// Searches for the canonical encoding, so that `text.find(uuid)` works like
+/// [`Uuid::find_in`].
+#[cfg(feature = "nightly")]
+impl std::str::pattern::Pattern for Uuid {
+    type Searcher<'a> = UuidSearcher<'a>;
+
+    fn into_searcher(self, haystack: &str) -> UuidSearcher<'_> {
+        UuidSearcher {
+            haystack,
+            needle: self.encode(),
+            position: 0,
+            end: haystack.len(),
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl std::str::pattern::Pattern for &Uuid {
+    type Searcher<'a> = UuidSearcher<'a>;
+
+    fn into_searcher(self, haystack: &str) -> UuidSearcher<'_> {
+        (*self).into_searcher(haystack)
+    }
+}
+
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+pub struct UuidSearcher<'a> {
+    haystack: &'a str,
+    needle: [u8; ENCODED_LEN],
+    // the unsearched `position..end`, shrinking from both sides
+    position: usize,
+    end: usize,
+}
+
+#[cfg(feature = "nightly")]
+impl UuidSearcher<'_> {
+    fn needle(&self) -> &str {
+        std::str::from_utf8(&self.needle).expect("crockford characters are ascii")
+    }
+}
+
+// SAFETY: every step starts where the previous one ended and the offsets are either
+// the haystack's end or come from `str::find`, so they lie on char boundaries
+#[cfg(feature = "nightly")]
+unsafe impl<'a> std::str::pattern::Searcher<'a> for UuidSearcher<'a> {
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> std::str::pattern::SearchStep {
+        use std::str::pattern::SearchStep;
+
+        let start = self.position;
+        if start == self.end {
+            return SearchStep::Done;
+        }
+        match self.haystack[start..self.end].find(self.needle()) {
+            Some(0) => {
+                self.position += ENCODED_LEN;
+                SearchStep::Match(start, self.position)
+            }
+            Some(offset) => {
+                self.position += offset;
+                SearchStep::Reject(start, self.position)
+            }
+            None => {
+                self.position = self.end;
+                SearchStep::Reject(start, self.position)
+            }
+        }
+    }
+}
+
+// SAFETY: as for the forward search, with offsets from `str::rfind`
+#[cfg(feature = "nightly")]
+unsafe impl<'a> std::str::pattern::ReverseSearcher<'a> for UuidSearcher<'a> {
+    fn next_back(&mut self) -> std::str::pattern::SearchStep {
+        use std::str::pattern::SearchStep;
+
+        let end = self.end;
+        if end == self.position {
+            return SearchStep::Done;
+        }
+        match self.haystack[self.position..end].rfind(self.needle()) {
+            Some(offset) if self.position + offset + ENCODED_LEN == end => {
+                self.end -= ENCODED_LEN;
+                SearchStep::Match(self.end, end)
+            }
+            Some(offset) => {
+                self.end = self.position + offset + ENCODED_LEN;
+                SearchStep::Reject(self.end, end)
+            }
+            None => {
+                self.end = self.position;
+                SearchStep::Reject(self.end, end)
+            }
+        }
+    }
+}
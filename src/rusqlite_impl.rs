@@ -0,0 +1,16 @@
+use crate::uuid::Uuid;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+/// The 15 raw bytes as a `BLOB`, like [`Uuid::to_sqlite_blob`].
+impl ToSql for Uuid {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_sqlite_blob()))
+    }
+}
+
+/// Reads a 15 byte `BLOB`, keeping the [`crate::UuidError`] of any other length.
+impl FromSql for Uuid {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Uuid::from_slice(value.as_blob()?).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
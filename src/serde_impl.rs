@@ -0,0 +1,102 @@
+use crate::error::UuidError;
+use crate::uuid::{Uuid, BYTE_SIZE};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::{Serialize, Serializer};
+
+/// The encoded string in human readable formats, the 15 raw bytes otherwise.
+impl Serialize for Uuid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.to_string_stack().as_str())
+        } else {
+            serializer.serialize_bytes(self.bytes.as_slice())
+        }
+    }
+}
+
+/// Accepts the encoded string, 15 bytes (as bytes or a sequence) or the numeric value
+/// (as an integer or a string of decimal digits) in self-describing formats. Other
+/// formats read the 15 bytes written by `Serialize`, or 25 bytes of encoded string.
+///
+/// A string of exactly 25 characters is always read as an encoded id, even if it is
+/// all digits.
+impl<'de> Deserialize<'de> for Uuid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(UuidVisitor)
+        } else {
+            deserializer.deserialize_bytes(UuidVisitor)
+        }
+    }
+}
+
+struct UuidVisitor;
+
+impl UuidVisitor {
+    fn from_value<E: de::Error>(value: u128) -> Result<Uuid, E> {
+        if value >> (BYTE_SIZE * 8) != 0 {
+            return Err(E::custom("numeric uuid exceeds 120 bits"));
+        }
+        Ok(Uuid::from_u128(value))
+    }
+}
+
+impl<'de> Visitor<'de> for UuidVisitor {
+    type Value = Uuid;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a crockford uuid string, 15 bytes or an unsigned 120 bit integer")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Uuid, E> {
+        if v.len() != Uuid::encoded_length_with_checksum()
+            && !v.is_empty()
+            && v.bytes().all(|b| b.is_ascii_digit())
+        {
+            let value = v
+                .parse::<u128>()
+                .map_err(|_| E::custom("numeric uuid exceeds 120 bits"))?;
+            return UuidVisitor::from_value(value);
+        }
+        Uuid::try_from(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Uuid, E> {
+        // compact formats such as Avro hand strings over as bytes too
+        if v.len() == Uuid::encoded_length_with_checksum() {
+            return Uuid::from_ascii(v).map_err(E::custom);
+        }
+        let bytes: [u8; BYTE_SIZE] = v
+            .try_into()
+            .map_err(|_| E::custom(UuidError::InvalidByteLength(v.len())))?;
+        Ok(Uuid::from_array(bytes))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Uuid, A::Error> {
+        let mut bytes = [0u8; BYTE_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::custom(UuidError::InvalidByteLength(
+                BYTE_SIZE + 1,
+            )));
+        }
+        Ok(Uuid::from_array(bytes))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Uuid, E> {
+        UuidVisitor::from_value(v as u128)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Uuid, E> {
+        UuidVisitor::from_value(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Uuid, E> {
+        let v = u64::try_from(v).map_err(|_| E::custom("numeric uuid is negative"))?;
+        self.visit_u64(v)
+    }
+}
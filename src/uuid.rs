@@ -0,0 +1,751 @@
+use crate::error::UuidError;
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+use std::io;
+use std::num::NonZeroU32;
+
+/// Number of bytes in a uuid.
+pub const BYTE_SIZE: usize = 15;
+// a prime number greater than 32 for checksum derivation
+const CROCKFORD_MODULO_PRIME: usize = 37;
+/// The checksum is the uuid as a 120 bit integer modulo this prime.
+pub const CHECKSUM_MODULO: usize = CROCKFORD_MODULO_PRIME;
+
+// SipHash-2-4 as specified by Aumasson and Bernstein, kept in tree so the output can
+// never change with a dependency or std upgrade
+fn siphash24(k0: u64, k1: u64, message: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+    let round = |v: &mut [u64; 4]| {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    };
+    let compress = |v: &mut [u64; 4], m: u64| {
+        v[3] ^= m;
+        round(v);
+        round(v);
+        v[0] ^= m;
+    };
+
+    let mut chunks = message.chunks_exact(8);
+    for chunk in &mut chunks {
+        compress(&mut v, u64::from_le_bytes(chunk.try_into().unwrap()));
+    }
+    // the last block holds the remaining bytes and the message length in the top byte
+    let mut last = [0u8; 8];
+    last[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+    last[7] = message.len() as u8;
+    compress(&mut v, u64::from_le_bytes(last));
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Bytes(pub(crate) [u8; BYTE_SIZE]);
+
+impl Bytes {
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+
+    #[cfg(feature = "bigint")]
+    pub fn to_int(self) -> BigUint {
+        BigUint::from_bytes_be(&self.0[..])
+    }
+
+    pub fn to_vec(self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    pub fn to_array(self) -> [u8; BYTE_SIZE] {
+        self.0
+    }
+
+    pub const fn derive_crockford_checksum(&self) -> u8 {
+        // horner's method keeps the running remainder well within u16
+        let mut remainder = 0u16;
+        let mut i = 0;
+        while i < BYTE_SIZE {
+            remainder = (remainder * 256 + self.0[i] as u16) % CROCKFORD_MODULO_PRIME as u16;
+            i += 1;
+        }
+        remainder as u8
+    }
+
+    pub fn new_with<F>(fill: F) -> Result<Self, String>
+    where
+        F: FnOnce(&mut [u8]) -> Result<(), ring::error::Unspecified>,
+    {
+        let mut bytes = [0; BYTE_SIZE];
+        fill(&mut bytes).map_err(|e| e.to_string())?;
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl TryFrom<BigUint> for Bytes {
+    type Error = UuidError;
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        let value = value.to_bytes_be();
+        if value.len() > BYTE_SIZE {
+            return Err(UuidError::InvalidByteLength(value.len()));
+        }
+        // big integers drop leading zero bytes
+        let mut bytes = [0; BYTE_SIZE];
+        bytes[BYTE_SIZE - value.len()..].copy_from_slice(&value);
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<Vec<u8>> for Bytes {
+    type Error = UuidError;
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        let bytes = value
+            .try_into()
+            .map_err(|value: Vec<u8>| UuidError::InvalidByteLength(value.len()))?;
+        Ok(Self(bytes))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Uuid {
+    pub(crate) bytes: Bytes,
+    pub(crate) checksum: u8,
+}
+
+impl Uuid {
+    /// A well known, obviously fake but valid uuid: `111111111111111111111111` plus its
+    /// checksum, `1111111111111111111111112`. Stable across crate versions.
+    pub const SENTINEL: Uuid = Uuid::from_array([
+        0x08, 0x42, 0x10, 0x84, 0x21, 0x08, 0x42, 0x10, 0x84, 0x21, 0x08, 0x42, 0x10, 0x84, 0x21,
+    ]);
+
+    /// Copies the 15 bytes of the uuid without allocating.
+    pub fn to_byte_array(&self) -> [u8; BYTE_SIZE] {
+        self.bytes.to_array()
+    }
+
+    /// The 15 raw bytes as an immutable, cheaply clonable [`bytes::Bytes`] handle.
+    #[cfg(feature = "bytes")]
+    pub fn to_bytes_frozen(&self) -> ::bytes::Bytes {
+        ::bytes::Bytes::copy_from_slice(self.bytes.as_slice())
+    }
+
+    /// The 15 raw bytes, for storing in an SQLite `BLOB` column.
+    pub fn to_sqlite_blob(&self) -> Vec<u8> {
+        self.bytes.to_vec()
+    }
+
+    /// Builds a uuid from exactly 15 raw bytes, recomputing the checksum. Fails with
+    /// [`UuidError::InvalidByteLength`] for any other length.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, UuidError> {
+        let array: [u8; BYTE_SIZE] = bytes
+            .try_into()
+            .map_err(|_| UuidError::InvalidByteLength(bytes.len()))?;
+        Ok(Uuid::from_array(array))
+    }
+
+    /// Reads a `BLOB` written by [`Uuid::to_sqlite_blob`], recomputing the checksum.
+    pub fn from_sqlite_blob(blob: &[u8]) -> Result<Self, UuidError> {
+        Uuid::from_slice(blob)
+    }
+
+    /// Builds a uuid from an id of another fixed size: shorter ids are left-padded with
+    /// zero bytes, longer ones are silently truncated to their first 15 bytes. Use
+    /// [`Uuid::from_bytes_fixed_strict`] to reject anything but exactly 15 bytes.
+    pub fn from_bytes_fixed<const N: usize>(bytes: &[u8; N]) -> Result<Self, UuidError> {
+        let mut padded = [0u8; BYTE_SIZE];
+        if N < BYTE_SIZE {
+            padded[BYTE_SIZE - N..].copy_from_slice(bytes);
+        } else {
+            padded.copy_from_slice(&bytes[..BYTE_SIZE]);
+        }
+        Ok(Uuid::from_array(padded))
+    }
+
+    /// Like [`Uuid::from_bytes_fixed`], but fails with [`UuidError::InvalidByteLength`]
+    /// unless `N` is 15.
+    pub fn from_bytes_fixed_strict<const N: usize>(bytes: &[u8; N]) -> Result<Self, UuidError> {
+        if N != BYTE_SIZE {
+            return Err(UuidError::InvalidByteLength(N));
+        }
+        Uuid::from_bytes_fixed(bytes)
+    }
+
+    /// The uuid as Java's `BigInteger.toByteArray()` writes it: big-endian two's
+    /// complement of minimal length. Leading zero bytes are dropped, and a zero byte is
+    /// prepended when the top bit would otherwise read as a sign, so the result is 1 to
+    /// 16 bytes long. Zero is `[0x00]`.
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let bytes = self.bytes.as_slice();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(BYTE_SIZE - 1);
+        let mut signed = Vec::with_capacity(BYTE_SIZE + 1);
+        if bytes[start] & 0x80 != 0 {
+            signed.push(0);
+        }
+        signed.extend_from_slice(&bytes[start..]);
+        signed
+    }
+
+    /// Reads a `BigInteger.toByteArray()` blob of 1 to 16 bytes, as written by
+    /// [`Uuid::to_signed_bytes_be`]. Redundant leading zero bytes are accepted, as
+    /// `new BigInteger(byte[])` does. Negative values and values over 120 bits fail with
+    /// [`UuidError::InvalidEncoding`].
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> Result<Self, UuidError> {
+        if bytes.is_empty() || bytes.len() > BYTE_SIZE + 1 {
+            return Err(UuidError::InvalidByteLength(bytes.len()));
+        }
+        if bytes[0] & 0x80 != 0 {
+            return Err(UuidError::InvalidEncoding(
+                "negative two's complement value".to_string(),
+            ));
+        }
+        let start = bytes.len() - bytes.len().min(BYTE_SIZE);
+        if bytes[..start].iter().any(|b| *b != 0) {
+            return Err(UuidError::InvalidEncoding(
+                "value exceeds 120 bits".to_string(),
+            ));
+        }
+        let mut padded = [0u8; BYTE_SIZE];
+        padded[BYTE_SIZE - (bytes.len() - start)..].copy_from_slice(&bytes[start..]);
+        Ok(Uuid::from_array(padded))
+    }
+
+    /// The raw 15 bytes of each uuid, back to back.
+    pub fn pack_multiple(uuids: &[Uuid]) -> Vec<u8> {
+        uuids
+            .iter()
+            .flat_map(|uuid| uuid.bytes.to_array())
+            .collect()
+    }
+
+    /// Splits a buffer written by [`Uuid::pack_multiple`], recomputing each checksum.
+    /// Fails with [`UuidError::InvalidByteLength`] unless the length is a multiple of 15.
+    pub fn unpack_multiple(data: &[u8]) -> Result<Vec<Self>, UuidError> {
+        if !data.len().is_multiple_of(BYTE_SIZE) {
+            return Err(UuidError::InvalidByteLength(data.len()));
+        }
+        data.chunks_exact(BYTE_SIZE).map(Uuid::from_slice).collect()
+    }
+
+    /// Writes the 15 raw bytes to `w`.
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.bytes.as_slice())
+    }
+
+    /// Reads exactly 15 raw bytes from `r`, recomputing the checksum. A stream that ends
+    /// early fails with [`io::ErrorKind::UnexpectedEof`].
+    pub fn read_from<R: io::Read>(r: &mut R) -> io::Result<Self> {
+        let mut bytes = [0u8; BYTE_SIZE];
+        r.read_exact(&mut bytes)?;
+        Ok(Uuid::from_array(bytes))
+    }
+
+    /// Writes each uuid with [`Uuid::write_to`], back to back as in
+    /// [`Uuid::pack_multiple`].
+    pub fn write_all_to<W: io::Write>(ids: &[Uuid], w: &mut W) -> io::Result<()> {
+        ids.iter().try_for_each(|uuid| uuid.write_to(w))
+    }
+
+    /// Reads `n` uuids written by [`Uuid::write_all_to`]. A stream that ends before the
+    /// last byte of the `n`th id fails with [`io::ErrorKind::UnexpectedEof`].
+    pub fn read_n_from<R: io::Read>(r: &mut R, n: usize) -> io::Result<Vec<Self>> {
+        (0..n).map(|_| Uuid::read_from(r)).collect()
+    }
+
+    /// A stable 64 bit hash of the uuid for bloom filters, sampling and join keys.
+    ///
+    /// Version 1 of the algorithm, guaranteed never to change: SipHash-2-4 keyed with
+    /// the bytes `0x00..=0x0f` (`k0 = 0x0706050403020100`, `k1 = 0x0f0e0d0c0b0a0908`)
+    /// over the 15 raw bytes of the uuid. The result does not depend on the platform
+    /// or its endianness. The key is public, so this is not a defence against
+    /// adversarially chosen ids.
+    pub fn fingerprint64(&self) -> u64 {
+        siphash24(
+            0x0706050403020100,
+            0x0f0e0d0c0b0a0908,
+            self.bytes.as_slice(),
+        )
+    }
+
+    /// The uuid with all 120 bits unset.
+    pub fn nil() -> Self {
+        Uuid::from_array([0; BYTE_SIZE])
+    }
+
+    /// The uuid with all 120 bits set.
+    pub fn max() -> Self {
+        Uuid::from_array([0xff; BYTE_SIZE])
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.bytes.as_slice().iter().all(|byte| *byte == 0)
+    }
+
+    /// [`Uuid::nil`] under a name that reads as deliberately fake in test code.
+    pub fn dev_null() -> Self {
+        Uuid::nil()
+    }
+
+    /// [`Uuid::is_nil`], the counterpart of [`Uuid::dev_null`].
+    pub fn is_dev_null(&self) -> bool {
+        self.is_nil()
+    }
+
+    /// The bytewise XOR of the two uuids.
+    pub fn xor(&self, other: &Uuid) -> [u8; BYTE_SIZE] {
+        let mut bytes = self.bytes.to_array();
+        for (byte, other) in bytes.iter_mut().zip(other.bytes.as_slice()) {
+            *byte ^= other;
+        }
+        bytes
+    }
+
+    /// The Kademlia XOR distance between the two uuids.
+    pub fn distance(&self, other: &Uuid) -> Distance {
+        Distance(self.xor(other))
+    }
+
+    pub(crate) fn to_u128(self) -> u128 {
+        let mut value = [0u8; 16];
+        value[1..].copy_from_slice(self.bytes.as_slice());
+        u128::from_be_bytes(value)
+    }
+
+    // keeps the low 120 bits of `value`
+    pub(crate) fn from_u128(value: u128) -> Self {
+        let mut bytes = [0u8; BYTE_SIZE];
+        bytes.copy_from_slice(&value.to_be_bytes()[1..]);
+        Uuid::from_array(bytes)
+    }
+
+    /// A uuid from its 15 raw bytes, computing the checksum. Usable in constants.
+    pub const fn from_byte_array(bytes: [u8; BYTE_SIZE]) -> Self {
+        Uuid::from_array(bytes)
+    }
+
+    pub(crate) const fn from_array(bytes: [u8; BYTE_SIZE]) -> Self {
+        let bytes = Bytes(bytes);
+        let checksum = bytes.derive_crockford_checksum();
+        Self { bytes, checksum }
+    }
+
+    /// Adds `offset` treating the uuid as a 120 bit unsigned integer, returning `None` on
+    /// overflow.
+    pub fn checked_add(&self, offset: u128) -> Option<Self> {
+        match self.overflowing_add(offset) {
+            (sum, false) => Some(sum),
+            (_, true) => None,
+        }
+    }
+
+    /// Subtracts `offset` treating the uuid as a 120 bit unsigned integer, returning `None`
+    /// when the result would be negative.
+    pub fn checked_sub(&self, offset: u128) -> Option<Self> {
+        match self.overflowing_sub(offset) {
+            (difference, false) => Some(difference),
+            (_, true) => None,
+        }
+    }
+
+    /// Adds `offset` modulo 2^120.
+    pub fn wrapping_add(&self, offset: u128) -> Self {
+        self.overflowing_add(offset).0
+    }
+
+    /// Subtracts `offset` modulo 2^120.
+    pub fn wrapping_sub(&self, offset: u128) -> Self {
+        self.overflowing_sub(offset).0
+    }
+
+    pub(crate) fn overflowing_add(&self, offset: u128) -> (Self, bool) {
+        let offset = offset.to_be_bytes();
+        let bytes = self.bytes.to_array();
+        let mut sum = [0u8; BYTE_SIZE];
+        let mut carry = false;
+        for i in (0..BYTE_SIZE).rev() {
+            let (value, first) = bytes[i].overflowing_add(offset[i + 1]);
+            let (value, second) = value.overflowing_add(carry as u8);
+            sum[i] = value;
+            carry = first || second;
+        }
+        // the top byte of the offset is beyond 120 bits
+        (Uuid::from_array(sum), carry || offset[0] != 0)
+    }
+
+    fn overflowing_sub(&self, offset: u128) -> (Self, bool) {
+        let offset = offset.to_be_bytes();
+        let bytes = self.bytes.to_array();
+        let mut difference = [0u8; BYTE_SIZE];
+        let mut borrow = false;
+        for i in (0..BYTE_SIZE).rev() {
+            let (value, first) = bytes[i].overflowing_sub(offset[i + 1]);
+            let (value, second) = value.overflowing_sub(borrow as u8);
+            difference[i] = value;
+            borrow = first || second;
+        }
+        (Uuid::from_array(difference), borrow || offset[0] != 0)
+    }
+}
+
+impl Default for Uuid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl TryFrom<BigUint> for Uuid {
+    type Error = UuidError;
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        let bytes: Bytes = value.try_into()?;
+        let checksum = bytes.derive_crockford_checksum();
+        Ok(Self { bytes, checksum })
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl From<Uuid> for BigUint {
+    fn from(value: Uuid) -> Self {
+        value.bytes.to_int()
+    }
+}
+
+impl From<Uuid> for Vec<u8> {
+    fn from(value: Uuid) -> Self {
+        value.bytes.to_vec()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<Uuid> for ::bytes::Bytes {
+    fn from(uuid: Uuid) -> Self {
+        uuid.to_bytes_frozen()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TryFrom<::bytes::Bytes> for Uuid {
+    type Error = UuidError;
+
+    fn try_from(value: ::bytes::Bytes) -> Result<Self, Self::Error> {
+        Uuid::from_slice(&value)
+    }
+}
+
+impl TryFrom<&[u8]> for Uuid {
+    type Error = UuidError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Uuid::from_slice(value)
+    }
+}
+
+impl From<Uuid> for Bytes {
+    fn from(value: Uuid) -> Self {
+        value.bytes
+    }
+}
+
+impl PartialEq<Uuid> for Uuid {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.to_crockford_base32_with_checksum() == other.to_crockford_base32_with_checksum()
+    }
+}
+
+impl Eq for Uuid {}
+
+impl std::hash::Hash for Uuid {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.bytes.as_slice().hash(state);
+    }
+}
+
+impl PartialOrd for Uuid {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Uuid {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.bytes.as_slice().cmp(other.bytes.as_slice())
+    }
+}
+
+// bytewise `op` of the two uuids, with the checksum recomputed
+fn bitwise(a: &Uuid, b: &Uuid, op: fn(u8, u8) -> u8) -> Uuid {
+    let mut bytes = a.bytes.to_array();
+    for (byte, other) in bytes.iter_mut().zip(b.bytes.as_slice()) {
+        *byte = op(*byte, *other);
+    }
+    Uuid::from_array(bytes)
+}
+
+// the owned, borrowed and assigning forms of a bytewise operator
+macro_rules! bitwise_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl std::ops::$trait for Uuid {
+            type Output = Uuid;
+
+            fn $method(self, rhs: Uuid) -> Uuid {
+                bitwise(&self, &rhs, |a, b| a $op b)
+            }
+        }
+
+        impl std::ops::$trait<&Uuid> for &Uuid {
+            type Output = Uuid;
+
+            fn $method(self, rhs: &Uuid) -> Uuid {
+                bitwise(self, rhs, |a, b| a $op b)
+            }
+        }
+
+        impl std::ops::$assign_trait for Uuid {
+            fn $assign_method(&mut self, rhs: Uuid) {
+                *self = bitwise(self, &rhs, |a, b| a $op b);
+            }
+        }
+
+        impl std::ops::$assign_trait<&Uuid> for Uuid {
+            fn $assign_method(&mut self, rhs: &Uuid) {
+                *self = bitwise(self, rhs, |a, b| a $op b);
+            }
+        }
+    };
+}
+
+bitwise_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+bitwise_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bitwise_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+
+impl std::ops::Not for Uuid {
+    type Output = Uuid;
+
+    fn not(self) -> Uuid {
+        !&self
+    }
+}
+
+impl std::ops::Not for &Uuid {
+    type Output = Uuid;
+
+    fn not(self) -> Uuid {
+        bitwise(self, &Uuid::max(), |a, b| a ^ b)
+    }
+}
+
+/// The byte at `index`, panicking past the 15th.
+impl std::ops::Index<usize> for Uuid {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        assert!(
+            index < BYTE_SIZE,
+            "uuid byte index {} out of range, a uuid has {} bytes",
+            index,
+            BYTE_SIZE
+        );
+        &self.bytes.0[index]
+    }
+}
+
+/// The bytes in `range`, panicking if it ends past the 15th.
+impl std::ops::Index<std::ops::Range<usize>> for Uuid {
+    type Output = [u8];
+
+    fn index(&self, range: std::ops::Range<usize>) -> &[u8] {
+        assert!(
+            range.end <= BYTE_SIZE,
+            "uuid byte range {:?} out of range, a uuid has {} bytes",
+            range,
+            BYTE_SIZE
+        );
+        &self.bytes.0[range]
+    }
+}
+
+/// The XOR distance between two uuids, ordered as a big-endian 120 bit integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Distance([u8; BYTE_SIZE]);
+
+impl Distance {
+    pub const ZERO: Distance = Distance([0; BYTE_SIZE]);
+
+    pub fn to_byte_array(&self) -> [u8; BYTE_SIZE] {
+        self.0
+    }
+
+    /// The number of leading zero bits out of 120, i.e. the length of the prefix the two
+    /// uuids share. Routing tables typically use `119 - leading_zeros()` as the bucket
+    /// index; a zero distance returns 120.
+    pub fn leading_zeros(&self) -> u32 {
+        let mut value = [0u8; 16];
+        value[1..].copy_from_slice(&self.0);
+        u128::from_be_bytes(value).leading_zeros() - 8
+    }
+}
+
+impl std::ops::BitXor for Distance {
+    type Output = Distance;
+
+    fn bitxor(mut self, rhs: Distance) -> Distance {
+        for (byte, other) in self.0.iter_mut().zip(rhs.0) {
+            *byte ^= other;
+        }
+        self
+    }
+}
+
+/// A uuid borrowed from 15 bytes stored elsewhere, e.g. a memory-mapped index of
+/// packed ids, to compare or format it without copying. Orders, hashes and compares
+/// equal like the owned [`Uuid`] with the same bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UuidRef<'a>(&'a [u8; BYTE_SIZE]);
+
+impl<'a> UuidRef<'a> {
+    pub const fn new(bytes: &'a [u8; BYTE_SIZE]) -> Self {
+        UuidRef(bytes)
+    }
+
+    /// Borrows exactly 15 bytes, failing with [`UuidError::InvalidByteLength`] otherwise.
+    pub fn from_slice(bytes: &'a [u8]) -> Result<Self, UuidError> {
+        bytes
+            .try_into()
+            .map(UuidRef)
+            .map_err(|_| UuidError::InvalidByteLength(bytes.len()))
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8; BYTE_SIZE] {
+        self.0
+    }
+
+    /// Copies the bytes into an owned [`Uuid`], computing its checksum.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_owned(&self) -> Uuid {
+        Uuid::from_array(*self.0)
+    }
+}
+
+impl<'a> From<&'a Uuid> for UuidRef<'a> {
+    fn from(uuid: &'a Uuid) -> Self {
+        UuidRef(&uuid.bytes.0)
+    }
+}
+
+impl std::fmt::Display for UuidRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.to_owned().fmt(f)
+    }
+}
+
+impl PartialEq<Uuid> for UuidRef<'_> {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.0 == &other.bytes.0
+    }
+}
+
+impl PartialEq<UuidRef<'_>> for Uuid {
+    fn eq(&self, other: &UuidRef<'_>) -> bool {
+        &self.bytes.0 == other.0
+    }
+}
+
+impl PartialOrd<Uuid> for UuidRef<'_> {
+    fn partial_cmp(&self, other: &Uuid) -> Option<std::cmp::Ordering> {
+        Some(self.0.as_slice().cmp(other.bytes.as_slice()))
+    }
+}
+
+impl PartialOrd<UuidRef<'_>> for Uuid {
+    fn partial_cmp(&self, other: &UuidRef<'_>) -> Option<std::cmp::Ordering> {
+        Some(self.bytes.as_slice().cmp(other.0.as_slice()))
+    }
+}
+
+/// A half-open range `[start, end)` of uuids in byte order, matching
+/// `WHERE id >= start AND id < end`. An `end` of `None` extends to the end of the
+/// 120 bit space, so that the range can include [`Uuid::max`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UuidRange {
+    start: Uuid,
+    end: Option<Uuid>,
+}
+
+impl UuidRange {
+    /// Creates the range `[start, end)`. An `end` before `start` gives an empty range.
+    pub fn new(start: Uuid, end: Option<Uuid>) -> Self {
+        Self { start, end }
+    }
+
+    /// The range covering every uuid.
+    pub fn full() -> Self {
+        Self::new(Uuid::nil(), None)
+    }
+
+    pub fn start(&self) -> &Uuid {
+        &self.start
+    }
+
+    /// The exclusive upper bound, `None` when the range extends past [`Uuid::max`].
+    pub fn end(&self) -> Option<&Uuid> {
+        self.end.as_ref()
+    }
+
+    pub fn contains(&self, uuid: &Uuid) -> bool {
+        *uuid >= self.start && self.end.as_ref().is_none_or(|end| uuid < end)
+    }
+
+    /// The number of uuids in the range.
+    pub fn len(&self) -> u128 {
+        let end = self.end.map_or(1 << 120, Uuid::to_u128);
+        end.saturating_sub(self.start.to_u128())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Splits the range into `n` contiguous, ordered sub-ranges that cover it exactly.
+    ///
+    /// Sizes differ by at most one, the first sub-ranges taking the remainder. A range
+    /// holding fewer than `n` uuids is split into single uuid ranges instead of
+    /// producing empty ones.
+    pub fn partition(&self, n: NonZeroU32) -> Vec<UuidRange> {
+        let len = self.len();
+        let parts = len.min(n.get() as u128);
+        if parts == 0 {
+            return Vec::new();
+        }
+
+        let (size, remainder) = (len / parts, len % parts);
+        let start = self.start.to_u128();
+        (0..parts)
+            .map(|i| {
+                let lo = start + i * size + i.min(remainder);
+                let end = if i == parts - 1 {
+                    self.end
+                } else {
+                    Some(Uuid::from_u128(lo + size + (i < remainder) as u128))
+                };
+                UuidRange::new(Uuid::from_u128(lo), end)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+//! Field validators for `String` ids in `validator` and `garde` derived structs.
+//!
+//! Where the format allows it, prefer declaring the field as a [`Uuid`] and letting
+//! serde reject bad ids while deserializing: the id is then parsed once and the rest
+//! of the code can't forget to parse it.
+
+use crate::error::UuidError;
+use crate::uuid::Uuid;
+
+/// A `validator` custom function, e.g. `#[validate(custom(function = "crockford_uuid::validate_str"))]`.
+///
+/// Errors have the code `crockford_uuid`, the parse error as their message and a
+/// `position` and `character` param for invalid characters.
+#[cfg(feature = "validator")]
+pub fn validate_str(value: &str) -> Result<(), validator::ValidationError> {
+    let err = match Uuid::try_from(value) {
+        Ok(_) => return Ok(()),
+        Err(err) => err,
+    };
+    let mut error =
+        validator::ValidationError::new("crockford_uuid").with_message(err.to_string().into());
+    if let UuidError::InvalidCharacter { position, .. } = err {
+        error.add_param("position".into(), &position);
+        if let Some(c) = value.get(position..).and_then(|s| s.chars().next()) {
+            error.add_param("character".into(), &c);
+        }
+    }
+    Err(error)
+}
+
+/// A `garde` custom rule, e.g. `#[garde(custom(crockford_uuid::garde_validate_str))]`,
+/// for any context. The error message is the parse error.
+#[cfg(feature = "garde")]
+pub fn garde_validate_str<C>(value: &str, _context: &C) -> garde::Result {
+    Uuid::try_from(value)
+        .map(|_| ())
+        .map_err(|err| garde::Error::new(err.to_string()))
+}
@@ -0,0 +1,9 @@
+//! Compile tests for `gen_uuid!`, which needs the `macros` feature.
+#![cfg(feature = "macros")]
+
+#[test]
+fn gen_uuid_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass_*.rs");
+    cases.compile_fail("tests/ui/fail_*.rs");
+}
@@ -0,0 +1,5 @@
+use crockford_uuid::gen_uuid;
+
+fn main() {
+    let _ = gen_uuid!(b"system-user");
+}
@@ -0,0 +1,7 @@
+use crockford_uuid::gen_uuid;
+
+const SEED: &str = "system-user";
+
+fn main() {
+    let _ = gen_uuid!(SEED);
+}
@@ -0,0 +1,11 @@
+use crockford_uuid::{gen_uuid, Uuid};
+
+const RANDOM: Uuid = gen_uuid!();
+const SEEDED: Uuid = gen_uuid!("system-user");
+const RAW: Uuid = gen_uuid!(r#"system-user"#);
+
+fn main() {
+    assert_ne!(RANDOM, SEEDED);
+    assert_eq!(SEEDED, RAW);
+    assert_eq!(SEEDED, Uuid::from_seed("system-user"));
+}